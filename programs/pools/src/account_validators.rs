@@ -1,8 +1,16 @@
 //! Validations for various accounts.
 
+use crate::import_pool::{self, ImportCheckAccounts};
 use crate::{
-    CommitNewAdmin, ImportPoolAsOperator, ImportPoolPermissionless, SendFeesToBeneficiary,
-    SetBeneficiary, SetOperator, SwapContext,
+    params, roles, AcceptBeneficiary, AcceptOperator, AdvanceAmpSchedule, ApplyScheduledFees,
+    ApproveProposal, BatchPauseSwaps, BatchSwapContext, CancelTimelockedAction, CheckDepeg,
+    CommitNewAdmin, CreateAmpSchedule, CreateMintOracle, CreateProposal, CreateRoleGrant,
+    CreatorSwapContext, EnableApprovals, ExecuteProposal, ExecuteTimelockedManagerAction,
+    ExecuteTimelockedSwapAction, ImportPoolAsOperator, ImportPoolPermissionless, PauseByMint,
+    PauseSwap, Pool, PoolManager, PoolManagerContext, Proposal, QueueTimelockedAction,
+    RecoverLamports, RecoverTokens, RoleGrant, ScheduleNewFees, SendFeesToBeneficiary,
+    SetAmpSchedule, SetApprovers, SetBeneficiary, SetGuardian, SetImportsEnabled, SetMintOracle,
+    SetOperator, SetPoolAdmin, SetRoles, SwapContext, TimelockedAction, TripCircuitBreaker,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
@@ -33,14 +41,37 @@ impl<'info> ImportPoolPermissionless<'info> {
         );
         invariant!(swap.fees == pm.initial_fees.into(), InitialFeesMismatch);
 
-        let token_a_mint = swap.token_a.mint;
-        let token_b_mint = swap.token_b.mint;
-        assert_keys_neq!(token_a_mint, token_b_mint, SwapTokensCannotBeEqual);
-        require!(token_a_mint < token_b_mint, SwapTokensNotSorted);
+        // the mints may be in either order, since the pool address uses the sorted mints
+        assert_keys_neq!(
+            swap.token_a.mint,
+            swap.token_b.mint,
+            SwapTokensCannotBeEqual
+        );
 
         Ok(())
     }
 
+    /// Runs the [crate::import_checks] enabled on the [PoolManager].
+    pub fn validate_import_checks(&self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let failed = import_pool::failed_import_checks(
+            &ImportCheckAccounts {
+                swap: &self.swap,
+                lp_mint: &self.lp_mint,
+                token_a_mint: &self.token_a_mint,
+                token_b_mint: &self.token_b_mint,
+                token_a_reserve: &self.token_a_reserve,
+                token_b_reserve: &self.token_b_reserve,
+            },
+            now,
+        ) & self.pool_manager.import_checks;
+        if failed != 0 {
+            msg!("Failed import checks: {:#b}", failed);
+            return err!(ImportChecksFailed);
+        }
+        Ok(())
+    }
+
     fn validate_fee_accounts(
         &self,
         fees: &Account<TokenAccount>,
@@ -58,27 +89,222 @@ impl<'info> ImportPoolPermissionless<'info> {
 impl<'info> Validate<'info> for ImportPoolAsOperator<'info> {
     fn validate(&self) -> Result<()> {
         invariant!(
-            self.admin_or_operator.key() == self.import_pool.pool_manager.admin
-                || self.admin_or_operator.key() == self.import_pool.pool_manager.operator,
+            self.import_pool.pool_manager.operator_imports_enabled,
+            ImportsDisabled
+        );
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(
+            self.import_pool
+                .pool_manager
+                .is_active_operator(&self.authority.key(), now)
+                || has_role(
+                    &self.import_pool.pool_manager,
+                    &self.authority,
+                    &self.role_grant,
+                    roles::IMPORTER
+                ),
             NotAdminOrOperator
         );
         Ok(())
     }
 }
 
-impl<'info> Validate<'info> for SwapContext<'info> {
+impl<'info> SwapContext<'info> {
+    /// Validates the accounts, requiring the authority to be the admin.
+    pub fn validate_admin(&self) -> Result<()> {
+        invariant!(is_admin(&self.pool_manager, &self.authority), NotAdmin);
+        self.validate_pool()
+    }
+
+    /// Validates the accounts, requiring the authority to be the admin, hold the role,
+    /// or be the [crate::Pool::pool_admin].
+    pub fn validate_role(&self, role: u16) -> Result<()> {
+        invariant!(
+            is_pool_admin(&self.pool_manager, &self.pool, &self.authority.key())
+                || has_role(&self.pool_manager, &self.authority, &self.role_grant, role),
+            MissingRole
+        );
+        self.validate_pool()
+    }
+
+    /// Validates the accounts for unpausing the swap.
+    /// Swaps paused by the circuit breaker may only be unpaused by the admin.
+    pub fn validate_unpause(&self) -> Result<()> {
+        if self.pool.circuit_breaker_tripped {
+            self.validate_admin()
+        } else {
+            self.validate_role(roles::PAUSER)
+        }
+    }
+
+    fn validate_pool(&self) -> Result<()> {
+        assert_keys_eq!(self.pool_manager, self.pool.manager);
+
+        assert_keys_eq!(self.swap, self.pool.swap);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CreatorSwapContext<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(self.pool.is_creator(&self.creator.key()), NotPoolCreator);
+        assert_keys_eq!(self.pool_manager, self.pool.manager);
+
+        assert_keys_eq!(self.swap, self.pool.swap);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CreateAmpSchedule<'info> {
+    fn validate(&self) -> Result<()> {
+        validate_admin_or_operator(&self.pool_manager, &self.authority)?;
+        assert_keys_eq!(self.pool_manager, self.pool.manager);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetAmpSchedule<'info> {
     fn validate(&self) -> Result<()> {
-        assert_keys_eq!(self.pool_manager.admin, self.admin, NotAdmin);
+        validate_admin_or_operator(&self.pool_manager, &self.authority)?;
         assert_keys_eq!(self.pool_manager, self.pool.manager);
+        assert_keys_eq!(self.pool, self.amp_schedule.pool);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for AdvanceAmpSchedule<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.pool_manager, self.pool.manager);
+        assert_keys_eq!(self.swap, self.pool.swap);
+        assert_keys_eq!(self.pool, self.amp_schedule.pool);
+        Ok(())
+    }
+}
+
+/// Checks that the authority may manage the amplification coefficient of swaps directly,
+/// as the admin or an active operator.
+fn validate_admin_or_operator<'info>(
+    pool_manager: &Account<'info, PoolManager>,
+    authority: &AccountInfo<'info>,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    invariant!(
+        is_admin(pool_manager, authority) || pool_manager.is_active_operator(authority.key, now),
+        NotAdminOrOperator
+    );
+    invariant!(!pool_manager.is_timelocked(), TimelockRequired);
+    Ok(())
+}
 
+impl<'info> Validate<'info> for TripCircuitBreaker<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.pool_manager, self.pool.manager);
         assert_keys_eq!(self.swap, self.pool.swap);
+        assert_keys_eq!(self.reserve_a, self.swap.token_a.reserves);
+        assert_keys_eq!(self.reserve_b, self.swap.token_b.reserves);
+        invariant!(
+            self.pool_manager.max_imbalance_bps != 0,
+            CircuitBreakerDisabled
+        );
+        // only the admin can clear a trip
+        invariant!(!self.pool_manager.is_admin_renounced(), AdminRenounced);
+        invariant!(!self.swap.is_paused, SwapAlreadyPaused);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CreateMintOracle<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(is_admin(&self.pool_manager, &self.admin), NotAdmin);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetMintOracle<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(is_admin(&self.pool_manager, &self.admin), NotAdmin);
+        assert_keys_eq!(self.pool_manager, self.mint_oracle.manager);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CheckDepeg<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.pool_manager, self.mint_oracle.manager);
+        assert_keys_eq!(self.price_account, self.mint_oracle.price_account);
+        // only the admin can clear a trip
+        invariant!(!self.pool_manager.is_admin_renounced(), AdminRenounced);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for PauseByMint<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(
+            is_admin(&self.pool_manager, &self.authority)
+                || self.pool_manager.is_guardian(&self.authority.key()),
+            NotAdminOrGuardian
+        );
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for PauseSwap<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(
+            self.pool_manager.is_guardian(&self.authority.key())
+                || is_pool_admin(&self.pool_manager, &self.pool, &self.authority.key())
+                || has_role(
+                    &self.pool_manager,
+                    &self.authority,
+                    &self.role_grant,
+                    roles::PAUSER
+                ),
+            NotAdminOrGuardian
+        );
+        assert_keys_eq!(self.pool_manager, self.pool.manager);
+
+        assert_keys_eq!(self.swap, self.pool.swap);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for BatchPauseSwaps<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(
+            self.pool_manager.is_guardian(&self.authority.key())
+                || has_role(
+                    &self.pool_manager,
+                    &self.authority,
+                    &self.role_grant,
+                    roles::PAUSER
+                ),
+            NotAdminOrGuardian
+        );
+        Ok(())
+    }
+}
+
+impl<'info> BatchSwapContext<'info> {
+    /// Returns true if the authority is the admin.
+    pub fn is_admin_authority(&self) -> bool {
+        is_admin(&self.pool_manager, &self.authority)
+    }
+
+    /// Validates the accounts, requiring the authority to be the admin or hold the role.
+    pub fn validate_role(&self, role: u16) -> Result<()> {
+        invariant!(
+            has_role(&self.pool_manager, &self.authority, &self.role_grant, role),
+            MissingRole
+        );
         Ok(())
     }
 }
 
 impl<'info> Validate<'info> for CommitNewAdmin<'info> {
     fn validate(&self) -> Result<()> {
-        assert_keys_eq!(self.pool_manager.admin, self.admin, NotAdmin);
+        invariant!(is_admin(&self.pool_manager, &self.admin), NotAdmin);
+        invariant!(!self.pool_manager.is_timelocked(), TimelockRequired);
         assert_keys_eq!(self.pool_manager, self.pool.manager);
 
         assert_keys_eq!(self.swap, self.pool.swap);
@@ -86,6 +312,39 @@ impl<'info> Validate<'info> for CommitNewAdmin<'info> {
     }
 }
 
+impl<'info> Validate<'info> for ScheduleNewFees<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(
+            is_pool_admin(&self.pool_manager, &self.pool, &self.authority.key())
+                || has_role(
+                    &self.pool_manager,
+                    &self.authority,
+                    &self.role_grant,
+                    roles::FEE_SETTER
+                ),
+            MissingRole
+        );
+        assert_keys_eq!(self.pool_manager, self.pool.manager);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for ApplyScheduledFees<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.pool_manager, self.pool.manager);
+        assert_keys_eq!(self.swap, self.pool.swap);
+
+        invariant!(self.pool.has_scheduled_fees(), NoScheduledFees);
+        invariant!(!self.pool_manager.is_locked(params::POOL_FEES), ParamLocked);
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(
+            now >= self.pool.scheduled_fees_effective_ts,
+            ScheduledFeesNotEffective
+        );
+        Ok(())
+    }
+}
+
 impl<'info> Validate<'info> for SendFeesToBeneficiary<'info> {
     fn validate(&self) -> Result<()> {
         assert_keys_eq!(self.pool_manager, self.pool.manager);
@@ -103,16 +362,369 @@ impl<'info> Validate<'info> for SendFeesToBeneficiary<'info> {
     }
 }
 
+impl<'info> Validate<'info> for RecoverTokens<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(
+            has_role(
+                &self.pool_manager,
+                &self.authority,
+                &self.role_grant,
+                roles::SWEEPER
+            ),
+            MissingRole
+        );
+        assert_keys_eq!(self.pool_manager, self.pool.manager);
+        assert_keys_eq!(self.swap, self.pool.swap);
+
+        assert_keys_eq!(self.token_account.owner, self.pool);
+        let token_account = self.token_account.key();
+        invariant!(
+            token_account != self.pool.token_a_fees
+                && token_account != self.pool.token_b_fees
+                && token_account != self.swap.token_a.reserves
+                && token_account != self.swap.token_b.reserves,
+            CannotRecoverPoolTokens
+        );
+        assert_keys_eq!(
+            self.beneficiary_account.owner,
+            self.pool_manager.beneficiary
+        );
+
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for RecoverLamports<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(
+            has_role(
+                &self.pool_manager,
+                &self.authority,
+                &self.role_grant,
+                roles::SWEEPER
+            ),
+            MissingRole
+        );
+        assert_keys_eq!(self.pool_manager, self.pool.manager);
+        assert_keys_eq!(self.beneficiary, self.pool_manager.beneficiary);
+
+        Ok(())
+    }
+}
+
 impl<'info> Validate<'info> for SetOperator<'info> {
     fn validate(&self) -> Result<()> {
-        assert_keys_eq!(self.pool_manager.admin, self.admin, NotAdmin);
+        invariant!(is_admin(&self.pool_manager, &self.admin), NotAdmin);
+        invariant!(!self.pool_manager.is_timelocked(), TimelockRequired);
+        invariant!(!self.pool_manager.is_locked(params::OPERATOR), ParamLocked);
         Ok(())
     }
 }
 
 impl<'info> Validate<'info> for SetBeneficiary<'info> {
     fn validate(&self) -> Result<()> {
-        assert_keys_eq!(self.pool_manager.admin, self.admin, NotAdmin);
+        invariant!(
+            has_role(
+                &self.pool_manager,
+                &self.authority,
+                &self.role_grant,
+                roles::BENEFICIARY_MANAGER
+            ),
+            MissingRole
+        );
+        invariant!(!self.pool_manager.is_timelocked(), TimelockRequired);
+        invariant!(
+            !self.pool_manager.is_locked(params::BENEFICIARY),
+            ParamLocked
+        );
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CreateRoleGrant<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(is_admin(&self.pool_manager, &self.admin), NotAdmin);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetRoles<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(is_admin(&self.pool_manager, &self.admin), NotAdmin);
+        assert_keys_eq!(self.pool_manager, self.role_grant.manager);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for AcceptOperator<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_neq!(self.pool_manager.pending_operator, Pubkey::default());
+        assert_keys_eq!(
+            self.pool_manager.pending_operator,
+            self.operator,
+            NotPendingOperator
+        );
+        invariant!(!self.pool_manager.is_locked(params::OPERATOR), ParamLocked);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for AcceptBeneficiary<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_neq!(self.pool_manager.pending_beneficiary, Pubkey::default());
+        assert_keys_eq!(
+            self.pool_manager.pending_beneficiary,
+            self.beneficiary,
+            NotPendingBeneficiary
+        );
+        invariant!(
+            !self.pool_manager.is_locked(params::BENEFICIARY),
+            ParamLocked
+        );
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetPoolAdmin<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(is_admin(&self.pool_manager, &self.admin), NotAdmin);
+        assert_keys_eq!(self.pool_manager, self.pool.manager);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetGuardian<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(is_admin(&self.pool_manager, &self.admin), NotAdmin);
         Ok(())
     }
 }
+
+impl<'info> SetImportsEnabled<'info> {
+    /// Validates the accounts, allowing the guardian to only disable imports.
+    pub fn validate_flags(
+        &self,
+        permissionless_imports_enabled: bool,
+        operator_imports_enabled: bool,
+    ) -> Result<()> {
+        if is_admin(&self.pool_manager, &self.authority) {
+            return Ok(());
+        }
+        invariant!(
+            self.pool_manager.is_guardian(&self.authority.key()),
+            NotAdminOrGuardian
+        );
+        invariant!(
+            (!permissionless_imports_enabled || self.pool_manager.permissionless_imports_enabled)
+                && (!operator_imports_enabled || self.pool_manager.operator_imports_enabled),
+            NotAdmin
+        );
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for PoolManagerContext<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(is_admin(&self.pool_manager, &self.admin), NotAdmin);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for QueueTimelockedAction<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(is_admin(&self.pool_manager, &self.admin), NotAdmin);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for ExecuteTimelockedManagerAction<'info> {
+    fn validate(&self) -> Result<()> {
+        validate_timelocked_action_executable(&self.pool_manager, &self.timelocked_action)?;
+        invariant!(
+            self.timelocked_action.action.pool().is_none(),
+            TimelockedActionKindMismatch
+        );
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for ExecuteTimelockedSwapAction<'info> {
+    fn validate(&self) -> Result<()> {
+        validate_timelocked_action_executable(&self.pool_manager, &self.timelocked_action)?;
+        invariant!(
+            self.timelocked_action.action.pool() == Some(self.pool.key()),
+            TimelockedActionKindMismatch
+        );
+        assert_keys_eq!(self.pool_manager, self.pool.manager);
+
+        assert_keys_eq!(self.swap, self.pool.swap);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CancelTimelockedAction<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(
+            is_admin(&self.pool_manager, &self.admin_or_guardian)
+                || self.pool_manager.is_guardian(&self.admin_or_guardian.key()),
+            NotAdminOrGuardian
+        );
+        assert_keys_eq!(self.pool_manager, self.timelocked_action.manager);
+        invariant!(
+            self.timelocked_action.executed_at == 0,
+            TimelockedActionAlreadyExecuted
+        );
+        invariant!(
+            self.timelocked_action.canceled_at == 0,
+            TimelockedActionCanceled
+        );
+        Ok(())
+    }
+}
+
+/// Checks that a [TimelockedAction] of the [PoolManager] may be executed now.
+fn validate_timelocked_action_executable(
+    pool_manager: &Account<PoolManager>,
+    timelocked_action: &Account<TimelockedAction>,
+) -> Result<()> {
+    assert_keys_eq!(pool_manager.key(), timelocked_action.manager);
+    invariant!(
+        timelocked_action.executed_at == 0,
+        TimelockedActionAlreadyExecuted
+    );
+    invariant!(timelocked_action.canceled_at == 0, TimelockedActionCanceled);
+    let now = Clock::get()?.unix_timestamp;
+    invariant!(now >= timelocked_action.eta, TimelockedActionNotReady);
+    invariant!(
+        now <= unwrap_int!(timelocked_action
+            .eta
+            .checked_add(crate::TIMELOCK_GRACE_PERIOD)),
+        TimelockedActionExpired
+    );
+    // the parameter may have been locked since the action was queued
+    invariant!(
+        !pool_manager.is_locked(timelocked_action.action.params()),
+        ParamLocked
+    );
+    Ok(())
+}
+
+impl<'info> Validate<'info> for EnableApprovals<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(is_admin(&self.pool_manager, &self.admin), NotAdmin);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for SetApprovers<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(is_admin(&self.pool_manager, &self.admin), NotAdmin);
+        assert_keys_eq!(self.pool_manager, self.approval_config.manager);
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for CreateProposal<'info> {
+    fn validate(&self) -> Result<()> {
+        invariant!(
+            self.approval_config
+                .approver_index(&self.proposer.key())
+                .is_some(),
+            NotApprover
+        );
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for ApproveProposal<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.approval_config.manager, self.proposal.manager);
+        invariant!(
+            self.proposal.approver_set_seqno == self.approval_config.approver_set_seqno,
+            ProposalApproversChanged
+        );
+        invariant!(self.proposal.executed_at == 0, ProposalAlreadyExecuted);
+        invariant!(
+            self.approval_config
+                .approver_index(&self.approver.key())
+                .is_some(),
+            NotApprover
+        );
+        Ok(())
+    }
+}
+
+impl<'info> Validate<'info> for ExecuteProposal<'info> {
+    fn validate(&self) -> Result<()> {
+        assert_keys_eq!(self.approval_config.manager, self.proposal.manager);
+        invariant!(
+            self.proposal.approver_set_seqno == self.approval_config.approver_set_seqno,
+            ProposalApproversChanged
+        );
+        invariant!(self.proposal.executed_at == 0, ProposalAlreadyExecuted);
+        invariant!(
+            self.proposal.num_approvals() >= u32::from(self.approval_config.threshold),
+            NotEnoughApprovals
+        );
+        Ok(())
+    }
+}
+
+/// Returns true if the account may act as the admin of the [PoolManager].
+///
+/// This is either [PoolManager::admin] or a [Proposal] of the [PoolManager].
+/// Once [PoolManager::approvals_required] is set, only [Proposal]s may act as the admin.
+/// Nobody may act as the admin once it has been renounced.
+/// [Proposal]s may only sign through [crate::pools::execute_proposal],
+/// so a signing [Proposal] has collected enough approvals.
+fn is_admin<'info>(
+    pool_manager: &Account<'info, PoolManager>,
+    account: &AccountInfo<'info>,
+) -> bool {
+    if pool_manager.is_admin_renounced() {
+        return false;
+    }
+    if *account.key == pool_manager.admin {
+        return !pool_manager.approvals_required;
+    }
+    account.is_signer
+        && *account.owner == crate::ID
+        && Account::<Proposal>::try_from(account)
+            .map(|proposal| proposal.manager == pool_manager.key())
+            .unwrap_or(false)
+}
+
+/// Returns true if the account is the [Pool::pool_admin].
+/// Pool admins are inactive once the admin of the [PoolManager] has been renounced.
+fn is_pool_admin(pool_manager: &PoolManager, pool: &Pool, key: &Pubkey) -> bool {
+    !pool_manager.is_admin_renounced() && pool.is_pool_admin(key)
+}
+
+/// Returns true if the authority is the admin of the [PoolManager],
+/// or holds the role through an unexpired [RoleGrant].
+/// [RoleGrant]s are inactive once the admin has been renounced.
+fn has_role<'info>(
+    pool_manager: &Account<'info, PoolManager>,
+    authority: &AccountInfo<'info>,
+    role_grant: &AccountInfo<'info>,
+    role: u16,
+) -> bool {
+    if pool_manager.is_admin_renounced() {
+        return false;
+    }
+    if is_admin(pool_manager, authority) {
+        return true;
+    }
+    let now = match Clock::get() {
+        Ok(clock) => clock.unix_timestamp,
+        Err(_) => return false,
+    };
+    *role_grant.owner == crate::ID
+        && Account::<RoleGrant>::try_from(role_grant)
+            .map(|grant| {
+                grant.manager == pool_manager.key()
+                    && grant.grantee == *authority.key
+                    && grant.has_roles(role, now)
+            })
+            .unwrap_or(false)
+}
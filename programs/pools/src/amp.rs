@@ -0,0 +1,104 @@
+//! Helpers for ramping the amplification coefficient of swaps.
+
+use crate::{Pool, RampPolicy};
+use anchor_lang::prelude::*;
+use stable_swap_anchor::SwapInfo;
+use vipers::prelude::*;
+
+/// Minimum amplification coefficient allowed by stable-swap.
+pub const MIN_AMP: u64 = 1;
+/// Maximum amplification coefficient allowed by stable-swap.
+pub const MAX_AMP: u64 = 1_000_000;
+/// Maximum factor by which stable-swap allows a ramp to change the amplification coefficient.
+pub const MAX_A_CHANGE: u64 = 10;
+/// Minimum number of seconds of a ramp, and between the starts of two ramps, in stable-swap.
+pub const MIN_RAMP_DURATION: i64 = 86_400;
+
+/// Computes the amplification coefficient of the [SwapInfo] at `now`,
+/// interpolating any ramp in progress in the same way as stable-swap.
+pub fn compute_amp_factor(swap: &SwapInfo, now: i64) -> Option<u64> {
+    if now >= swap.stop_ramp_ts {
+        return Some(swap.target_amp_factor);
+    }
+
+    let time_range = u128::try_from(swap.stop_ramp_ts.checked_sub(swap.start_ramp_ts)?).ok()?;
+    let time_delta = u128::try_from(now.checked_sub(swap.start_ramp_ts)?).ok()?;
+    let initial = u128::from(swap.initial_amp_factor);
+    let target = u128::from(swap.target_amp_factor);
+
+    let amp_factor = if target > initial {
+        let delta = (target - initial)
+            .checked_mul(time_delta)?
+            .checked_div(time_range)?;
+        initial.checked_add(delta)?
+    } else {
+        let delta = (initial - target)
+            .checked_mul(time_delta)?
+            .checked_div(time_range)?;
+        initial.checked_sub(delta)?
+    };
+    u64::try_from(amp_factor).ok()
+}
+
+/// Checks that stable-swap will accept ramping the [SwapInfo] to `target_amp` at `now`,
+/// so that bad ramps fail with a descriptive error rather than inside the CPI.
+pub fn validate_swap_ramp(
+    swap: &SwapInfo,
+    target_amp: u64,
+    stop_ramp_ts: i64,
+    now: i64,
+) -> Result<()> {
+    invariant!(now >= swap.stop_ramp_ts, RampInProgress);
+    invariant!(
+        now >= unwrap_int!(swap.start_ramp_ts.checked_add(MIN_RAMP_DURATION)),
+        RampLocked
+    );
+    invariant!(
+        stop_ramp_ts >= unwrap_int!(now.checked_add(MIN_RAMP_DURATION)),
+        InsufficientRampTime
+    );
+    invariant!(
+        (MIN_AMP..=MAX_AMP).contains(&target_amp),
+        TargetAmpOutOfRange
+    );
+
+    let current_amp = unwrap_int!(compute_amp_factor(swap, now));
+    // stable-swap compares the truncated ratio
+    let change = if target_amp < current_amp {
+        unwrap_int!(current_amp.checked_div(target_amp))
+    } else {
+        unwrap_int!(target_amp.checked_div(current_amp))
+    };
+    invariant!(change <= MAX_A_CHANGE, AmpChangeExceedsSwapLimit);
+    Ok(())
+}
+
+/// Checks that a ramp of the [Pool] is allowed by stable-swap and the [RampPolicy],
+/// then records it as the last ramp of the [Pool].
+pub fn record_ramp(
+    policy: &RampPolicy,
+    pool: &mut Pool,
+    swap: &SwapInfo,
+    target_amp: u64,
+    stop_ramp_ts: i64,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    validate_swap_ramp(swap, target_amp, stop_ramp_ts, now)?;
+
+    invariant!(
+        now >= unwrap_int!(pool.last_ramp_ts.checked_add(policy.min_cooldown)),
+        RampCooldownNotElapsed
+    );
+    invariant!(
+        stop_ramp_ts >= unwrap_int!(now.checked_add(policy.min_duration)),
+        RampTooShort
+    );
+    let current_amp = unwrap_int!(compute_amp_factor(swap, now));
+    invariant!(
+        policy.allows_change(current_amp, target_amp),
+        RampChangeTooLarge
+    );
+
+    pool.last_ramp_ts = now;
+    Ok(())
+}
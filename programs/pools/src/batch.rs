@@ -0,0 +1,36 @@
+//! Helpers for operating on many [Pool]s at once.
+
+use crate::{Pool, PoolManager};
+use anchor_lang::prelude::*;
+use stable_swap_anchor::SwapInfo;
+use vipers::prelude::*;
+
+/// A [Pool] and the [SwapInfo] it administers.
+pub struct PoolSwapPair<'info> {
+    pub pool: Account<'info, Pool>,
+    pub swap: Account<'info, SwapInfo>,
+}
+
+/// Loads `(pool, swap)` pairs from the remaining accounts of an instruction.
+///
+/// Each pair is validated in the same way as a [crate::SwapContext].
+pub fn load_pool_swap_pairs<'info>(
+    pool_manager: &Account<'info, PoolManager>,
+    remaining_accounts: &[AccountInfo<'info>],
+) -> Result<Vec<PoolSwapPair<'info>>> {
+    invariant!(
+        !remaining_accounts.is_empty() && remaining_accounts.len().is_multiple_of(2),
+        InvalidBatchAccounts
+    );
+    remaining_accounts
+        .chunks(2)
+        .map(|pair| {
+            let pool: Account<Pool> = Account::try_from(&pair[0])?;
+            let swap: Account<SwapInfo> = Account::try_from(&pair[1])?;
+            assert_keys_eq!(pool.manager, pool_manager.key());
+            assert_keys_eq!(pool.swap, swap.key());
+            invariant!(pool.is_migrated(), AccountNeedsMigration);
+            Ok(PoolSwapPair { pool, swap })
+        })
+        .collect()
+}
@@ -0,0 +1,100 @@
+//! Helpers for the reserve-imbalance circuit breaker.
+//!
+//! The imbalance is measured from the reserves of the swap at the time of the call,
+//! which anyone may move with a large enough trade. Tripping the circuit breaker
+//! therefore only costs the price impact of such a trade, and
+//! [crate::PoolManager::max_imbalance_bps] should be set well above the imbalance
+//! a trade can cheaply cause.
+
+use stable_swap_client::state::SwapInfo;
+use stable_swap_math::bn::U192;
+use stable_swap_math::curve::{StableSwap, N_COINS};
+
+/// Number of bps in 100%.
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// Number of bits the reserves are scaled down to before computing the marginal price,
+/// which keeps its intermediate products within a [U192].
+const PRICE_PRECISION_BITS: u32 = 32;
+
+/// Computes the imbalance of the reserves of a swap at `now`, in bps.
+///
+/// The imbalance is the deviation of the marginal price of the swap from 1,
+/// `max(p, 1 / p) - 1`, as given by the stable-swap invariant. The more amplified
+/// the swap, the more the reserves must diverge for the price to move.
+/// Both tokens of a swap have the same decimals, so the raw amounts are comparable.
+/// Empty reserves are balanced; a single empty reserve is maximally imbalanced.
+///
+/// The invariant is computed with stable-swap's own math. Returns [None] if it overflows
+/// or did not converge.
+pub fn imbalance_bps(swap: &SwapInfo, now: i64, reserve_a: u64, reserve_b: u64) -> Option<u64> {
+    if reserve_a == reserve_b {
+        return Some(0);
+    }
+    if reserve_a == 0 || reserve_b == 0 {
+        return Some(u64::MAX);
+    }
+
+    let curve = StableSwap::new_from_swap_info(swap, now);
+    // stable-swap uses `A * n` for two tokens
+    let ann = U192::from(curve.compute_amp_factor()?.checked_mul(N_COINS.into())?);
+    let d = curve.compute_d(reserve_a, reserve_b)?;
+    // stable-swap returns its last estimate if the iteration limit is reached
+    if !is_converged(ann, d, reserve_a, reserve_b)? {
+        return None;
+    }
+
+    // the price is homogeneous in the reserves and the invariant, so they may be scaled
+    // down together
+    let max_bits = u64::BITS - reserve_a.max(reserve_b).leading_zeros();
+    let shift = max_bits.saturating_sub(PRICE_PRECISION_BITS);
+    let x = U192::from(reserve_a >> shift);
+    let y = U192::from(reserve_b >> shift);
+    if x.is_zero() || y.is_zero() {
+        return Some(u64::MAX);
+    }
+    let d = d >> shift;
+
+    // with F = Ann (x + y) + D - Ann D - D^3 / (4xy), the marginal price is
+    // dF/dx / dF/dy = (4 Ann x^2 y^2 + D^3 y) / (4 Ann x^2 y^2 + D^3 x)
+    let d_cubed = d.checked_mul(d)?.checked_mul(d)?;
+    let xy = x.checked_mul(y)?;
+    let leverage = ann
+        .checked_mul(4.into())?
+        .checked_mul(xy)?
+        .checked_mul(xy)?;
+    let (diff, min) = if x > y {
+        (x.checked_sub(y)?, y)
+    } else {
+        (y.checked_sub(x)?, x)
+    };
+    let bps = d_cubed
+        .checked_mul(diff)?
+        .checked_mul(BPS_DENOMINATOR.into())?
+        .checked_div(leverage.checked_add(d_cubed.checked_mul(min)?)?)?;
+    // saturates at u64::MAX
+    Some(bps.to_u64().unwrap_or(u64::MAX))
+}
+
+/// Returns whether `d` is a fixed point of stable-swap's Newton iteration for the invariant,
+/// that is, whether another step would move it by at most 1.
+fn is_converged(ann: U192, d: U192, reserve_a: u64, reserve_b: u64) -> Option<bool> {
+    let n_coins = U192::from(N_COINS);
+    let sum = U192::from(reserve_a).checked_add(reserve_b.into())?;
+    let d_prod = d
+        .checked_mul(d)?
+        .checked_div(U192::from(reserve_a).checked_mul(n_coins)?)?
+        .checked_mul(d)?
+        .checked_div(U192::from(reserve_b).checked_mul(n_coins)?)?;
+    let next_d = ann
+        .checked_mul(sum)?
+        .checked_add(d_prod.checked_mul(n_coins)?)?
+        .checked_mul(d)?
+        .checked_div(
+            ann.checked_sub(1.into())?
+                .checked_mul(d)?
+                .checked_add(d_prod.checked_mul(n_coins.checked_add(1.into())?)?)?,
+        )?;
+    let delta = if next_d > d { next_d - d } else { d - next_d };
+    Some(delta <= 1.into())
+}
@@ -1,13 +1,20 @@
-use crate::ImportPoolPermissionless;
+use crate::{
+    import_checks, import_failures, CheckImport, ImportPoolPermissionless, Pool, SortedMints,
+};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
+use anchor_spl::token::{Mint, TokenAccount};
+use stable_swap_anchor::SwapInfo;
+use stable_swap_client::state::SwapTokenInfo;
 use vipers::unwrap_int;
 use vipers::Validate;
 
 /// Import pool without validating initial parameters.
+/// Pools imported with a `creator` are marked as permissionlessly imported.
 pub fn import_pool_unchecked(
     accounts: &mut ImportPoolPermissionless,
     bump: u8,
-    permissionless_import: bool,
+    creator: Option<Pubkey>,
 ) -> Result<()> {
     accounts.validate()?;
 
@@ -29,7 +36,160 @@ pub fn import_pool_unchecked(
     // Metadata for clients
     pool.lp_mint = accounts.lp_mint.key();
     pool.token_decimals = accounts.lp_mint.decimals;
-    pool.permissionless_import = permissionless_import;
+    pool.permissionless_import = creator.is_some();
+    pool.swap_tokens_reversed = accounts.swap.token_a.mint > accounts.swap.token_b.mint;
+    if let Some(creator) = creator {
+        pool.creator = creator;
+    }
+    pool.version = Pool::VERSION;
 
     Ok(())
 }
+
+/// Accounts of a swap inspected by [failed_import_checks].
+pub struct ImportCheckAccounts<'a, 'info> {
+    pub swap: &'a Account<'info, SwapInfo>,
+    pub lp_mint: &'a Mint,
+    pub token_a_mint: &'a Mint,
+    pub token_b_mint: &'a Mint,
+    pub token_a_reserve: &'a TokenAccount,
+    pub token_b_reserve: &'a TokenAccount,
+}
+
+/// Runs every [import_checks] check on a swap, returning the bitmask of checks which failed.
+pub fn failed_import_checks(accounts: &ImportCheckAccounts, now: i64) -> u16 {
+    let swap = accounts.swap;
+    let mut failed = 0;
+
+    if accounts.token_a_mint.freeze_authority.is_some()
+        || accounts.token_b_mint.freeze_authority.is_some()
+    {
+        failed |= import_checks::NO_MINT_FREEZE_AUTHORITY;
+    }
+
+    let lp_mint_authority_is_swap = Pubkey::create_program_address(
+        &[&swap.key().to_bytes(), &[swap.nonce]],
+        swap.to_account_info().owner,
+    )
+    .map(|swap_authority| accounts.lp_mint.mint_authority == COption::Some(swap_authority))
+    .unwrap_or(false);
+    if !lp_mint_authority_is_swap {
+        failed |= import_checks::LP_MINT_AUTHORITY_IS_SWAP;
+    }
+    if accounts.lp_mint.freeze_authority.is_some() {
+        failed |= import_checks::NO_LP_FREEZE_AUTHORITY;
+    }
+
+    if swap.is_paused {
+        failed |= import_checks::NOT_PAUSED;
+    }
+    if now < swap.stop_ramp_ts {
+        failed |= import_checks::NO_RAMP_IN_PROGRESS;
+    }
+    if accounts.token_a_reserve.amount == 0 || accounts.token_b_reserve.amount == 0 {
+        failed |= import_checks::NONZERO_RESERVES;
+    }
+    if swap.future_admin_key != Pubkey::default() || swap.future_admin_deadline != 0 {
+        failed |= import_checks::NO_PENDING_ADMIN;
+    }
+
+    failed
+}
+
+/// Runs every check of [crate::pools::import_pool_permissionless] without stopping at the
+/// first failure, returning the bitmask of [import_failures].
+pub fn diagnose_import(accounts: &CheckImport, now: i64) -> u32 {
+    let swap = &accounts.swap;
+    let pool_manager = &accounts.pool_manager;
+    let mut failed = 0;
+
+    if !pool_manager.permissionless_imports_enabled {
+        failed |= import_failures::IMPORTS_DISABLED;
+    }
+    if swap.admin_key != accounts.pool.key() {
+        failed |= import_failures::SWAP_ADMIN_NOT_POOL;
+    }
+    if swap.pool_mint != accounts.lp_mint.key() {
+        failed |= import_failures::LP_MINT_MISMATCH;
+    }
+    failed |= diagnose_fee_account(accounts, &accounts.token_a_fees, &swap.token_a);
+    failed |= diagnose_fee_account(accounts, &accounts.token_b_fees, &swap.token_b);
+
+    if swap.initial_amp_factor < pool_manager.min_permissionless_amp_factor
+        || swap.target_amp_factor > pool_manager.max_permissionless_amp_factor
+    {
+        failed |= import_failures::AMP_OUT_OF_RANGE;
+    }
+    if swap.fees != pool_manager.initial_fees.into() {
+        failed |= import_failures::FEES_MISMATCH;
+    }
+    if swap.token_a.mint == swap.token_b.mint {
+        failed |= import_failures::MINTS_EQUAL;
+    }
+
+    let (mint_a, mint_b) = swap.sorted_mints();
+    let (pool_key, _) = Pubkey::find_program_address(
+        &[
+            b"SaberPool".as_ref(),
+            &pool_manager.key().to_bytes(),
+            &mint_a.to_bytes(),
+            &mint_b.to_bytes(),
+        ],
+        &crate::ID,
+    );
+    if accounts.pool.key() != pool_key {
+        failed |= import_failures::POOL_ADDRESS_MISMATCH;
+    }
+    if !accounts.pool.data_is_empty() {
+        failed |= import_failures::POOL_EXISTS;
+    }
+
+    if accounts.token_a_mint.key() != swap.token_a.mint
+        || accounts.token_b_mint.key() != swap.token_b.mint
+        || accounts.token_a_reserve.key() != swap.token_a.reserves
+        || accounts.token_b_reserve.key() != swap.token_b.reserves
+    {
+        // the import checks would inspect the wrong accounts
+        failed |= import_failures::TOKEN_ACCOUNT_MISMATCH;
+    } else {
+        let failed_checks = failed_import_checks(
+            &ImportCheckAccounts {
+                swap,
+                lp_mint: &accounts.lp_mint,
+                token_a_mint: &accounts.token_a_mint,
+                token_b_mint: &accounts.token_b_mint,
+                token_a_reserve: &accounts.token_a_reserve,
+                token_b_reserve: &accounts.token_b_reserve,
+            },
+            now,
+        ) & pool_manager.import_checks;
+        failed |= u32::from(failed_checks) << import_failures::IMPORT_CHECKS_SHIFT;
+    }
+
+    failed
+}
+
+/// Runs the checks of a fee account, returning the bitmask of [import_failures].
+fn diagnose_fee_account(
+    accounts: &CheckImport,
+    fees: &Account<TokenAccount>,
+    swap_token_info: &SwapTokenInfo,
+) -> u32 {
+    let mut failed = 0;
+    if fees.owner != accounts.pool.key() {
+        failed |= import_failures::FEE_ACCOUNT_OWNER;
+    }
+    if fees.mint != swap_token_info.mint {
+        failed |= import_failures::FEE_ACCOUNT_MINT;
+    }
+    if fees.delegate.is_some() {
+        failed |= import_failures::FEE_ACCOUNT_DELEGATE;
+    }
+    if fees.close_authority.is_some() {
+        failed |= import_failures::FEE_ACCOUNT_CLOSE_AUTHORITY;
+    }
+    if fees.key() != swap_token_info.admin_fees {
+        failed |= import_failures::FEE_ACCOUNT_MISMATCH;
+    }
+    failed
+}
@@ -19,19 +19,42 @@
 mod macros;
 
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::Instruction,
+    program::{invoke_signed, set_return_data},
+};
+use anchor_lang::{AccountsExit, Discriminator};
 use anchor_spl::token::{Mint, Token, TokenAccount};
+use batch::PoolSwapPair;
 use stable_swap_anchor::{StableSwap, SwapInfo};
 use vipers::prelude::*;
 
 mod account_validators;
+mod amp;
+mod batch;
+mod circuit_breaker;
 mod cpi_helpers;
 mod import_pool;
+mod migrate;
+mod oracle;
 mod state;
 
 pub use state::*;
 
 declare_id!("SMANK4F5osjfVpKFH5LPzE6HPpbzSPu5iHPBhuor5xU");
 
+/// Number of seconds between committing new [FeeCaps] and being able to apply them.
+pub const FEE_CAPS_CHANGE_DELAY: i64 = 7 * 86_400;
+
+/// Number of seconds between committing a new [RampPolicy] and being able to apply it.
+pub const RAMP_POLICY_CHANGE_DELAY: i64 = 7 * 86_400;
+
+/// Default [PoolManager::swap_admin_change_delay].
+pub const SWAP_ADMIN_CHANGE_DELAY: i64 = 7 * 86_400;
+
+/// Number of seconds after its ETA during which a [TimelockedAction] may be executed.
+pub const TIMELOCK_GRACE_PERIOD: i64 = 14 * 86_400;
+
 /// [pools] program.
 #[program]
 pub mod pools {
@@ -65,8 +88,19 @@ pub mod pools {
         pool_manager.max_permissionless_amp_factor = 200;
 
         pool_manager.operator = ctx.accounts.operator.key();
+        pool_manager.operator_valid_until = i64::MAX;
         pool_manager.beneficiary = ctx.accounts.beneficiary.key();
 
+        pool_manager.min_fee_notice_period = PoolManager::DEFAULT_MIN_FEE_NOTICE_PERIOD;
+        pool_manager.fee_caps = FeeCaps::DEFAULT;
+        pool_manager.ramp_policy = RampPolicy::DEFAULT;
+        pool_manager.swap_admin_change_delay = SWAP_ADMIN_CHANGE_DELAY;
+
+        pool_manager.permissionless_imports_enabled = true;
+        pool_manager.operator_imports_enabled = true;
+
+        pool_manager.version = PoolManager::VERSION;
+
         Ok(())
     }
 
@@ -79,21 +113,86 @@ pub mod pools {
         ctx: Context<ImportPoolPermissionless>,
         _bump: u8,
     ) -> Result<()> {
+        invariant!(
+            ctx.accounts.pool_manager.permissionless_imports_enabled,
+            ImportsDisabled
+        );
         ctx.accounts.validate_initial_parameters()?;
+        ctx.accounts.validate_import_checks()?;
         let bump = unwrap_bump!(ctx, "pool");
-        import_pool::import_pool_unchecked(ctx.accounts, bump, true)
+        let creator = ctx.accounts.payer.key();
+        import_pool::import_pool_unchecked(ctx.accounts, bump, Some(creator))
+    }
+
+    /// Runs every check of [pools::import_pool_permissionless] without creating anything,
+    /// setting the bitmask of [import_failures] as the return data.
+    /// Zero means the swap may be imported.
+    pub fn check_import(ctx: Context<CheckImport>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let failed = import_pool::diagnose_import(ctx.accounts, now);
+        set_return_data(&failed.to_le_bytes());
+        Ok(())
+    }
+
+    /// Imports a [Pool] from a [SwapInfo] whose admin has committed the [Pool] as its new admin.
+    /// The previous admin of the [SwapInfo] must sign, which applies the new admin,
+    /// and becomes the [Pool::creator].
+    /// The [SwapInfo] must then pass every check of [pools::import_pool_permissionless].
+    pub fn adopt_pool(ctx: Context<AdoptPool>, _bump: u8) -> Result<()> {
+        let bump = unwrap_bump!(ctx, "pool");
+        let creator = ctx.accounts.previous_admin.key();
+        let accounts = &mut ctx.accounts.import_pool;
+        invariant!(
+            accounts.pool_manager.permissionless_imports_enabled,
+            ImportsDisabled
+        );
+        assert_keys_eq!(accounts.swap.admin_key, creator, NotSwapAdmin);
+        assert_keys_eq!(
+            accounts.swap.future_admin_key,
+            accounts.pool,
+            SwapAdminNotCommitted
+        );
+
+        // stable-swap requires its current admin to apply the new admin
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.swap_program.to_account_info(),
+            stable_swap_anchor::AdminUserContext {
+                admin: ctx.accounts.previous_admin.to_account_info(),
+                swap: ctx.accounts.swap.to_account_info(),
+            },
+        );
+        stable_swap_anchor::apply_new_admin(cpi_ctx)?;
+        accounts.swap.reload()?;
+
+        accounts.validate()?;
+        accounts.validate_initial_parameters()?;
+        accounts.validate_import_checks()?;
+        import_pool::import_pool_unchecked(accounts, bump, Some(creator))
     }
 
     /// Imports a pool as the [PoolManager]'s operator.
     #[access_control(ctx.accounts.validate())]
     pub fn import_pool_as_operator(ctx: Context<ImportPoolAsOperator>, _bump: u8) -> Result<()> {
         let bump = unwrap_bump!(ctx, "pool");
-        import_pool::import_pool_unchecked(&mut ctx.accounts.import_pool, bump, false)
+        import_pool::import_pool_unchecked(&mut ctx.accounts.import_pool, bump, None)
     }
 
     /// Ramp [SwapInfo]'s amplification coefficient to some target amplification coefficient.
-    #[access_control(ctx.accounts.validate())]
+    #[access_control(ctx.accounts.validate_role(roles::RAMPER))]
     pub fn ramp_a(ctx: Context<SwapContext>, target_amp: u64, stop_ramp_ts: i64) -> Result<()> {
+        invariant!(!ctx.accounts.pool_manager.is_timelocked(), TimelockRequired);
+        invariant!(
+            !ctx.accounts.pool_manager.is_locked(params::POOL_RAMPS),
+            ParamLocked
+        );
+        amp::record_ramp(
+            &ctx.accounts.pool_manager.ramp_policy,
+            &mut ctx.accounts.pool,
+            &ctx.accounts.swap,
+            target_amp,
+            stop_ramp_ts,
+        )?;
+
         let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(ctx.accounts.pool);
         let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
             &ctx.accounts.pool,
@@ -105,8 +204,14 @@ pub mod pools {
     }
 
     /// Stop ramping amplification coefficent.
-    #[access_control(ctx.accounts.validate())]
+    /// Like pausing, stopping a ramp is not subject to the timelock.
+    #[access_control(ctx.accounts.validate_role(roles::RAMPER))]
     pub fn stop_ramp_a(ctx: Context<SwapContext>) -> Result<()> {
+        invariant!(
+            !ctx.accounts.pool_manager.is_locked(params::POOL_RAMPS),
+            ParamLocked
+        );
+
         let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(ctx.accounts.pool);
         let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
             &ctx.accounts.pool,
@@ -117,9 +222,30 @@ pub mod pools {
         stable_swap_anchor::stop_ramp_a(cpi_ctx)
     }
 
-    /// Pause the swap.
+    /// Ramps the amplification coefficient of a permissionlessly imported [Pool]
+    /// as its [Pool::creator].
+    /// The target must be within the permissionless amp factor bounds of the [PoolManager].
     #[access_control(ctx.accounts.validate())]
-    pub fn pause_swap(ctx: Context<SwapContext>) -> Result<()> {
+    pub fn creator_ramp_a(
+        ctx: Context<CreatorSwapContext>,
+        target_amp: u64,
+        stop_ramp_ts: i64,
+    ) -> Result<()> {
+        let pool_manager = &ctx.accounts.pool_manager;
+        invariant!(!pool_manager.is_locked(params::POOL_RAMPS), ParamLocked);
+        invariant!(
+            target_amp >= pool_manager.min_permissionless_amp_factor
+                && target_amp <= pool_manager.max_permissionless_amp_factor,
+            AmpOutOfRange
+        );
+        amp::record_ramp(
+            &ctx.accounts.pool_manager.ramp_policy,
+            &mut ctx.accounts.pool,
+            &ctx.accounts.swap,
+            target_amp,
+            stop_ramp_ts,
+        )?;
+
         let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(ctx.accounts.pool);
         let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
             &ctx.accounts.pool,
@@ -127,12 +253,18 @@ pub mod pools {
             ctx.accounts.swap_program.to_account_info(),
         )
         .with_signer(seeds);
-        stable_swap_anchor::pause(cpi_ctx)
+        stable_swap_anchor::ramp_a(cpi_ctx, target_amp, stop_ramp_ts)
     }
 
-    /// Unpause the swap.
+    /// Stops ramping the amplification coefficient of a permissionlessly imported [Pool]
+    /// as its [Pool::creator].
     #[access_control(ctx.accounts.validate())]
-    pub fn unpause_swap(ctx: Context<SwapContext>) -> Result<()> {
+    pub fn creator_stop_ramp_a(ctx: Context<CreatorSwapContext>) -> Result<()> {
+        invariant!(
+            !ctx.accounts.pool_manager.is_locked(params::POOL_RAMPS),
+            ParamLocked
+        );
+
         let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(ctx.accounts.pool);
         let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
             &ctx.accounts.pool,
@@ -140,32 +272,80 @@ pub mod pools {
             ctx.accounts.swap_program.to_account_info(),
         )
         .with_signer(seeds);
-        stable_swap_anchor::unpause(cpi_ctx)
+        stable_swap_anchor::stop_ramp_a(cpi_ctx)
     }
 
-    /// Commits a new admin to [SwapInfo].
+    /// Creates the [AmpSchedule] of a [Pool].
+    /// This may be called by the admin or the operator.
     #[access_control(ctx.accounts.validate())]
-    pub fn commit_new_admin(ctx: Context<CommitNewAdmin>) -> Result<()> {
-        let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(ctx.accounts.pool);
+    pub fn create_amp_schedule(
+        ctx: Context<CreateAmpSchedule>,
+        steps: Vec<AmpScheduleStep>,
+    ) -> Result<()> {
+        validate_amp_schedule_steps(&steps)?;
+
+        let amp_schedule = &mut ctx.accounts.amp_schedule;
+        amp_schedule.pool = ctx.accounts.pool.key();
+        amp_schedule.bump = unwrap_bump!(ctx, "amp_schedule");
+        amp_schedule.next_step = 0;
+        amp_schedule.steps = steps;
+
+        Ok(())
+    }
+
+    /// Replaces the steps of an [AmpSchedule], starting again from the first step.
+    /// This may be called by the admin or the operator.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_amp_schedule(
+        ctx: Context<SetAmpSchedule>,
+        steps: Vec<AmpScheduleStep>,
+    ) -> Result<()> {
+        validate_amp_schedule_steps(&steps)?;
+
+        let amp_schedule = &mut ctx.accounts.amp_schedule;
+        amp_schedule.next_step = 0;
+        amp_schedule.steps = steps;
+
+        Ok(())
+    }
+
+    /// Starts the next ramp of an [AmpSchedule] once the current ramp has ended.
+    /// Anyone may call this.
+    /// [AmpSchedule]s cannot go through the timelock, so they may not be advanced
+    /// while the [PoolManager] is timelocked.
+    #[access_control(ctx.accounts.validate())]
+    pub fn advance_amp_schedule(ctx: Context<AdvanceAmpSchedule>) -> Result<()> {
+        invariant!(!ctx.accounts.pool_manager.is_timelocked(), TimelockRequired);
+        invariant!(
+            !ctx.accounts.pool_manager.is_locked(params::POOL_RAMPS),
+            ParamLocked
+        );
+        let step = unwrap_opt!(ctx.accounts.amp_schedule.next(), AmpScheduleComplete);
+        amp::record_ramp(
+            &ctx.accounts.pool_manager.ramp_policy,
+            &mut ctx.accounts.pool,
+            &ctx.accounts.swap,
+            step.target_amp,
+            step.stop_ramp_ts,
+        )?;
+
+        let amp_schedule = &mut ctx.accounts.amp_schedule;
+        amp_schedule.next_step = unwrap_int!(amp_schedule.next_step.checked_add(1));
 
-        let admin_user_context = cpi_helpers::create_pool_admin_user_context(
+        let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(ctx.accounts.pool);
+        let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
             &ctx.accounts.pool,
             ctx.accounts.swap.to_account_info(),
-        );
-        let cpi_ctx = CpiContext::new_with_signer(
             ctx.accounts.swap_program.to_account_info(),
-            stable_swap_anchor::CommitNewAdmin {
-                admin_ctx: admin_user_context,
-                new_admin: ctx.accounts.new_admin.to_account_info(),
-            },
-            seeds,
-        );
-        stable_swap_anchor::commit_new_admin(cpi_ctx)
+        )
+        .with_signer(seeds);
+        stable_swap_anchor::ramp_a(cpi_ctx, step.target_amp, step.stop_ramp_ts)
     }
 
-    /// Apply the new admin on [SwapInfo].
+    /// Pause the swap.
+    /// This may be called by the admin, the guardian, the [Pool::pool_admin], or a [roles::PAUSER].
     #[access_control(ctx.accounts.validate())]
-    pub fn apply_new_admin(ctx: Context<SwapContext>) -> Result<()> {
+    pub fn pause_swap(ctx: Context<PauseSwap>) -> Result<()> {
         let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(ctx.accounts.pool);
         let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
             &ctx.accounts.pool,
@@ -173,12 +353,15 @@ pub mod pools {
             ctx.accounts.swap_program.to_account_info(),
         )
         .with_signer(seeds);
-        stable_swap_anchor::apply_new_admin(cpi_ctx)
+        stable_swap_anchor::pause(cpi_ctx)
     }
 
-    /// Set new fees on the [SwapInfo].
-    #[access_control(ctx.accounts.validate())]
-    pub fn set_new_fees(ctx: Context<SwapContext>, new_fees: SwapFees) -> Result<()> {
+    /// Unpause the swap.
+    /// If the swap was paused by the circuit breaker, only the admin may unpause it.
+    #[access_control(ctx.accounts.validate_unpause())]
+    pub fn unpause_swap(ctx: Context<SwapContext>) -> Result<()> {
+        ctx.accounts.pool.circuit_breaker_tripped = false;
+
         let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(ctx.accounts.pool);
         let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
             &ctx.accounts.pool,
@@ -186,175 +369,1769 @@ pub mod pools {
             ctx.accounts.swap_program.to_account_info(),
         )
         .with_signer(seeds);
-        stable_swap_anchor::set_new_fees(cpi_ctx, new_fees.into())
+        stable_swap_anchor::unpause(cpi_ctx)
     }
 
-    /// Sends fees on a [Pool] fee account to an ATA controlled by the beneficiary.
+    /// Pauses the swap if its reserves are more imbalanced than
+    /// [PoolManager::max_imbalance_bps] allows, accounting for its amplification coefficient.
     /// Anyone may call this.
+    ///
+    /// The imbalance is read from the current reserves, which a large trade can move
+    /// within the same transaction, so the threshold should be set well above the
+    /// imbalance a trade can cheaply cause.
+    ///
+    /// Only the admin may unpause a tripped swap, so this is disabled once the admin
+    /// is renounced.
     #[access_control(ctx.accounts.validate())]
-    pub fn send_fees_to_beneficiary(ctx: Context<SendFeesToBeneficiary>) -> Result<()> {
+    pub fn trip_circuit_breaker(ctx: Context<TripCircuitBreaker>) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let imbalance_bps = unwrap_opt!(
+            circuit_breaker::imbalance_bps(
+                &ctx.accounts.swap,
+                now,
+                ctx.accounts.reserve_a.amount,
+                ctx.accounts.reserve_b.amount,
+            ),
+            ImbalanceNotComputable
+        );
+        invariant!(
+            imbalance_bps > u64::from(ctx.accounts.pool_manager.max_imbalance_bps),
+            ImbalanceWithinThreshold
+        );
+        ctx.accounts.pool.circuit_breaker_tripped = true;
+
         let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(ctx.accounts.pool);
-        token::transfer(
-            CpiContext::new(
-                ctx.accounts.token_program.to_account_info(),
-                token::Transfer {
-                    from: ctx.accounts.fee_account.to_account_info(),
-                    to: ctx.accounts.beneficiary_account.to_account_info(),
-                    authority: ctx.accounts.pool.to_account_info(),
-                },
-            )
-            .with_signer(seeds),
-            ctx.accounts.fee_account.amount,
+        let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
+            &ctx.accounts.pool,
+            ctx.accounts.swap.to_account_info(),
+            ctx.accounts.swap_program.to_account_info(),
         )
+        .with_signer(seeds);
+        stable_swap_anchor::pause(cpi_ctx)
     }
 
-    /// Sets the [PoolManager::operator].
+    /// Creates the [MintOracle] of a [Mint].
     #[access_control(ctx.accounts.validate())]
-    pub fn set_operator(ctx: Context<SetOperator>) -> Result<()> {
-        let pool_manager = &mut ctx.accounts.pool_manager;
-        pool_manager.operator = ctx.accounts.operator.key();
+    pub fn create_mint_oracle(
+        ctx: Context<CreateMintOracle>,
+        peg_price: i64,
+        peg_expo: i32,
+        max_deviation_bps: u16,
+        max_staleness_slots: u64,
+    ) -> Result<()> {
+        invariant!(
+            peg_price > 0 && max_deviation_bps > 0 && max_deviation_bps <= 10_000,
+            InvalidOracleConfig
+        );
+
+        let mint_oracle = &mut ctx.accounts.mint_oracle;
+        mint_oracle.manager = ctx.accounts.pool_manager.key();
+        mint_oracle.mint = ctx.accounts.mint.key();
+        mint_oracle.bump = unwrap_bump!(ctx, "mint_oracle");
+
+        mint_oracle.price_account = ctx.accounts.price_account.key();
+        mint_oracle.peg_price = peg_price;
+        mint_oracle.peg_expo = peg_expo;
+        mint_oracle.max_deviation_bps = max_deviation_bps;
+        mint_oracle.max_staleness_slots = max_staleness_slots;
 
         Ok(())
     }
 
-    /// Sets the [PoolManager::beneficiary].
+    /// Sets the price account and band of a [MintOracle].
     #[access_control(ctx.accounts.validate())]
-    pub fn set_beneficiary(ctx: Context<SetBeneficiary>) -> Result<()> {
-        let pool_manager = &mut ctx.accounts.pool_manager;
-        pool_manager.beneficiary = ctx.accounts.beneficiary.key();
+    pub fn set_mint_oracle(
+        ctx: Context<SetMintOracle>,
+        peg_price: i64,
+        peg_expo: i32,
+        max_deviation_bps: u16,
+        max_staleness_slots: u64,
+    ) -> Result<()> {
+        invariant!(
+            peg_price > 0 && max_deviation_bps > 0 && max_deviation_bps <= 10_000,
+            InvalidOracleConfig
+        );
+
+        let mint_oracle = &mut ctx.accounts.mint_oracle;
+        mint_oracle.price_account = ctx.accounts.price_account.key();
+        mint_oracle.peg_price = peg_price;
+        mint_oracle.peg_expo = peg_expo;
+        mint_oracle.max_deviation_bps = max_deviation_bps;
+        mint_oracle.max_staleness_slots = max_staleness_slots;
 
         Ok(())
     }
-}
-
-/// Accounts for [pools::new_pool_manager].
-#[derive(Accounts)]
-pub struct NewPoolManager<'info> {
-    /// The [PoolManager].
-    #[account(
-        init,
-        seeds = [
-            b"SaberPoolManager".as_ref(),
-            base.key().to_bytes().as_ref()
-        ],
-        bump,
-        space = 8 + PoolManager::LEN,
-        payer = payer
-    )]
-    pub pool_manager: Account<'info, PoolManager>,
 
-    /// Base key.
-    pub base: Signer<'info>,
+    /// Pauses swaps holding the mint of a [MintOracle] once its price has deviated
+    /// from the peg by more than [MintOracle::max_deviation_bps].
+    /// The `(pool, swap)` pairs must be passed via `remaining_accounts`.
+    /// Like the circuit breaker, only the admin may unpause these swaps,
+    /// so this is disabled once the admin is renounced.
+    /// Anyone may call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn check_depeg<'info>(ctx: Context<'_, '_, '_, 'info, CheckDepeg<'info>>) -> Result<()> {
+        let mint_oracle = &ctx.accounts.mint_oracle;
+        let price = unwrap_opt!(
+            oracle::parse_price(&ctx.accounts.price_account.try_borrow_data()?),
+            InvalidPriceAccount
+        );
+        let slot = Clock::get()?.slot;
+        invariant!(
+            slot.saturating_sub(price.pub_slot) <= mint_oracle.max_staleness_slots,
+            StaleOraclePrice
+        );
+        let deviation_bps = unwrap_int!(oracle::deviation_bps(
+            price.price,
+            price.expo,
+            mint_oracle.peg_price,
+            mint_oracle.peg_expo
+        ));
+        invariant!(
+            deviation_bps > u64::from(mint_oracle.max_deviation_bps),
+            PriceWithinBand
+        );
 
-    /// Initial admin of the [PoolManager].
-    /// CHECK: Initializer.
-    pub admin: UncheckedAccount<'info>,
+        for PoolSwapPair { mut pool, swap } in
+            batch::load_pool_swap_pairs(&ctx.accounts.pool_manager, ctx.remaining_accounts)?
+        {
+            invariant!(
+                pool.mint_a == mint_oracle.mint || pool.mint_b == mint_oracle.mint,
+                PoolMintMismatch
+            );
+            pool.circuit_breaker_tripped = true;
+            pool.exit(&crate::ID)?;
 
-    /// Initial operator of the [PoolManager].
-    /// CHECK: Initializer.
-    pub operator: UncheckedAccount<'info>,
+            let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(pool);
+            let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
+                &pool,
+                swap.to_account_info(),
+                ctx.accounts.swap_program.to_account_info(),
+            )
+            .with_signer(seeds);
+            stable_swap_anchor::pause(cpi_ctx)?;
+        }
+        Ok(())
+    }
 
-    /// Initial beneficiary of the [PoolManager].
-    /// CHECK: Initializer.
-    pub beneficiary: UncheckedAccount<'info>,
+    /// Pauses every swap holding a [Mint] and records a [MintIncident].
+    /// The `(pool, swap)` pairs must be passed via `remaining_accounts`.
+    /// This may be called by the admin or the guardian.
+    #[access_control(ctx.accounts.validate())]
+    pub fn pause_by_mint<'info>(ctx: Context<'_, '_, '_, 'info, PauseByMint<'info>>) -> Result<()> {
+        let mint = ctx.accounts.mint.key();
+        let pairs =
+            batch::load_pool_swap_pairs(&ctx.accounts.pool_manager, ctx.remaining_accounts)?;
+        let num_swaps_paused = pairs.len() as u64;
+        for PoolSwapPair { pool, swap } in pairs {
+            invariant!(pool.mint_a == mint || pool.mint_b == mint, PoolMintMismatch);
 
-    /// Payer of the [PoolManager] initialization.
-    #[account(mut)]
-    pub payer: Signer<'info>,
+            let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(pool);
+            let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
+                &pool,
+                swap.to_account_info(),
+                ctx.accounts.swap_program.to_account_info(),
+            )
+            .with_signer(seeds);
+            stable_swap_anchor::pause(cpi_ctx)?;
+        }
 
-    /// [System] program.
-    pub system_program: Program<'info, System>,
-}
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        let mint_incident = &mut ctx.accounts.mint_incident;
+        mint_incident.manager = pool_manager.key();
+        mint_incident.index = pool_manager.num_mint_incidents;
+        mint_incident.bump = unwrap_bump!(ctx, "mint_incident");
 
-/// Accounts for [pools::import_pool_permissionless].
-#[derive(Accounts)]
-pub struct ImportPoolPermissionless<'info> {
-    /// The [PoolManager].
-    #[account(mut)]
-    pub pool_manager: Box<Account<'info, PoolManager>>,
+        mint_incident.mint = mint;
+        mint_incident.authority = ctx.accounts.authority.key();
+        mint_incident.num_swaps_paused = num_swaps_paused;
+        mint_incident.created_at = Clock::get()?.unix_timestamp;
 
-    /// [SwapInfo] to import.
-    pub swap: Box<Account<'info, SwapInfo>>,
+        pool_manager.num_mint_incidents =
+            unwrap_int!(pool_manager.num_mint_incidents.checked_add(1));
 
-    /// [Pool].
-    #[account(
-        init,
-        seeds = [
-            b"SaberPool".as_ref(),
-            pool_manager.key().to_bytes().as_ref(),
-            swap.sorted_mints().0.to_bytes().as_ref(),
-            swap.sorted_mints().1.to_bytes().as_ref()
-        ],
-        bump,
-        space = 8 + Pool::LEN,
-        payer = payer
-    )]
-    pub pool: Box<Account<'info, Pool>>,
+        Ok(())
+    }
 
-    /// Fee account for token A.
-    #[account(address = swap.token_a.admin_fees)]
-    pub token_a_fees: Box<Account<'info, TokenAccount>>,
+    /// Commits a new admin of the [SwapInfo].
+    /// The new admin may be applied via [pools::apply_new_admin] after
+    /// [PoolManager::swap_admin_change_delay].
+    #[access_control(ctx.accounts.validate())]
+    pub fn commit_new_admin(ctx: Context<CommitNewAdmin>) -> Result<()> {
+        invariant!(
+            !ctx.accounts.pool_manager.is_locked(params::SWAP_ADMIN),
+            ParamLocked
+        );
+        let now = Clock::get()?.unix_timestamp;
+        let delay = ctx.accounts.pool_manager.swap_admin_change_delay;
+        let pool = &mut ctx.accounts.pool;
+        pool.pending_swap_admin = ctx.accounts.new_admin.key();
+        pool.pending_swap_admin_effective_ts = unwrap_int!(now.checked_add(delay));
 
-    /// Fee account for token B.
-    #[account(address = swap.token_b.admin_fees)]
-    pub token_b_fees: Box<Account<'info, TokenAccount>>,
+        Ok(())
+    }
 
-    /// Mint of the LP token.
-    pub lp_mint: Box<Account<'info, Mint>>,
+    /// Hands the [SwapInfo] over to the [Pool::pending_swap_admin].
+    /// The new admin must be passed as the first remaining account.
+    #[access_control(ctx.accounts.validate_admin())]
+    pub fn apply_new_admin<'info>(
+        ctx: Context<'_, '_, '_, 'info, SwapContext<'info>>,
+    ) -> Result<()> {
+        invariant!(
+            !ctx.accounts.pool_manager.is_locked(params::SWAP_ADMIN),
+            ParamLocked
+        );
+        let pool = &ctx.accounts.pool;
+        invariant!(
+            pool.pending_swap_admin_effective_ts != 0,
+            NoPendingSwapAdmin
+        );
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(
+            now >= pool.pending_swap_admin_effective_ts,
+            PendingSwapAdminNotEffective
+        );
+        let new_admin_info = ctx
+            .remaining_accounts
+            .first()
+            .ok_or_else(|| error!(ErrorCode::MissingNewAdmin))?;
+        assert_keys_eq!(
+            new_admin_info.key(),
+            pool.pending_swap_admin,
+            MissingNewAdmin
+        );
 
-    /// Payer of the [Pool] initialization.
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    /// [System] program.
-    pub system_program: Program<'info, System>,
-}
+        let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(pool);
+        let admin_user_context =
+            cpi_helpers::create_pool_admin_user_context(pool, ctx.accounts.swap.to_account_info());
+        stable_swap_anchor::commit_new_admin(CpiContext::new_with_signer(
+            ctx.accounts.swap_program.to_account_info(),
+            stable_swap_anchor::CommitNewAdmin {
+                admin_ctx: admin_user_context,
+                new_admin: new_admin_info.clone(),
+            },
+            seeds,
+        ))?;
+        let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
+            pool,
+            ctx.accounts.swap.to_account_info(),
+            ctx.accounts.swap_program.to_account_info(),
+        )
+        .with_signer(seeds);
+        stable_swap_anchor::apply_new_admin(cpi_ctx)?;
 
-/// Accounts for [pools::import_pool_as_operator].
-#[derive(Accounts)]
-pub struct ImportPoolAsOperator<'info> {
-    /// The admin or operator of the [PoolManager].
-    pub admin_or_operator: Signer<'info>,
-    /// Import pool accounts.
-    pub import_pool: ImportPoolPermissionless<'info>,
-}
+        let pool = &mut ctx.accounts.pool;
+        pool.pending_swap_admin = Pubkey::default();
+        pool.pending_swap_admin_effective_ts = 0;
 
-/// Accounts for admin-related swap operations.
-#[derive(Accounts)]
-pub struct SwapContext<'info> {
-    #[account(has_one = admin @ ErrorCode::NotAdmin)]
-    pub pool_manager: Account<'info, PoolManager>,
-    #[account(mut)]
-    pub swap: Account<'info, SwapInfo>,
-    #[account(
-        has_one = swap,
-        constraint = pool.manager == pool_manager.key()
-    )]
-    pub pool: Account<'info, Pool>,
-    pub swap_program: Program<'info, StableSwap>,
-    pub admin: Signer<'info>,
-}
+        Ok(())
+    }
 
-#[derive(Accounts)]
-pub struct CommitNewAdmin<'info> {
-    #[account(
-        address = pool.manager,
-        has_one = admin @ ErrorCode::NotAdmin
-    )]
-    pub pool_manager: Account<'info, PoolManager>,
-    #[account(mut)]
-    pub swap: Account<'info, SwapInfo>,
-    #[account(has_one = swap)]
-    pub pool: Account<'info, Pool>,
-    pub admin: Signer<'info>,
-    /// CHECK: Arbitrary.
-    pub new_admin: UncheckedAccount<'info>,
-    pub swap_program: Program<'info, StableSwap>,
-}
+    /// Set new fees on the [SwapInfo].
+    /// Fees may only be set immediately if [PoolManager::min_fee_notice_period] is zero;
+    /// otherwise they must be scheduled via [pools::schedule_new_fees].
+    #[access_control(ctx.accounts.validate_role(roles::FEE_SETTER))]
+    pub fn set_new_fees(ctx: Context<SwapContext>, new_fees: SwapFees) -> Result<()> {
+        invariant!(!ctx.accounts.pool_manager.is_timelocked(), TimelockRequired);
+        invariant!(
+            ctx.accounts.pool_manager.min_fee_notice_period == 0,
+            FeeNoticeRequired
+        );
+        invariant!(
+            !ctx.accounts.pool_manager.is_locked(params::POOL_FEES),
+            ParamLocked
+        );
+        invariant!(
+            ctx.accounts.pool_manager.fee_caps.allows(&new_fees),
+            FeesExceedCaps
+        );
+
+        let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(ctx.accounts.pool);
+        let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
+            &ctx.accounts.pool,
+            ctx.accounts.swap.to_account_info(),
+            ctx.accounts.swap_program.to_account_info(),
+        )
+        .with_signer(seeds);
+        stable_swap_anchor::set_new_fees(cpi_ctx, new_fees.into())
+    }
+
+    /// Pauses many swaps at once.
+    /// The `(pool, swap)` pairs must be passed via `remaining_accounts`.
+    /// This may be called by the admin, the guardian, or a [roles::PAUSER].
+    #[access_control(ctx.accounts.validate())]
+    pub fn pause_swaps<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchPauseSwaps<'info>>,
+    ) -> Result<()> {
+        for PoolSwapPair { pool, swap } in
+            batch::load_pool_swap_pairs(&ctx.accounts.pool_manager, ctx.remaining_accounts)?
+        {
+            let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(pool);
+            let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
+                &pool,
+                swap.to_account_info(),
+                ctx.accounts.swap_program.to_account_info(),
+            )
+            .with_signer(seeds);
+            stable_swap_anchor::pause(cpi_ctx)?;
+        }
+        Ok(())
+    }
+
+    /// Unpauses many swaps at once.
+    /// The `(pool, swap)` pairs must be passed via `remaining_accounts`.
+    /// Swaps paused by the circuit breaker may only be unpaused by the admin.
+    #[access_control(ctx.accounts.validate_role(roles::PAUSER))]
+    pub fn unpause_swaps<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchSwapContext<'info>>,
+    ) -> Result<()> {
+        for PoolSwapPair { mut pool, swap } in
+            batch::load_pool_swap_pairs(&ctx.accounts.pool_manager, ctx.remaining_accounts)?
+        {
+            if pool.circuit_breaker_tripped {
+                invariant!(ctx.accounts.is_admin_authority(), NotAdmin);
+                pool.circuit_breaker_tripped = false;
+                pool.exit(&crate::ID)?;
+            }
+
+            let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(pool);
+            let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
+                &pool,
+                swap.to_account_info(),
+                ctx.accounts.swap_program.to_account_info(),
+            )
+            .with_signer(seeds);
+            stable_swap_anchor::unpause(cpi_ctx)?;
+        }
+        Ok(())
+    }
+
+    /// Sets the same new fees on many swaps at once.
+    /// The `(pool, swap)` pairs must be passed via `remaining_accounts`.
+    /// Like [pools::set_new_fees], this requires [PoolManager::min_fee_notice_period] to be zero.
+    #[access_control(ctx.accounts.validate_role(roles::FEE_SETTER))]
+    pub fn set_new_fees_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchSwapContext<'info>>,
+        new_fees: SwapFees,
+    ) -> Result<()> {
+        invariant!(!ctx.accounts.pool_manager.is_timelocked(), TimelockRequired);
+        invariant!(
+            ctx.accounts.pool_manager.min_fee_notice_period == 0,
+            FeeNoticeRequired
+        );
+        invariant!(
+            !ctx.accounts.pool_manager.is_locked(params::POOL_FEES),
+            ParamLocked
+        );
+        invariant!(
+            ctx.accounts.pool_manager.fee_caps.allows(&new_fees),
+            FeesExceedCaps
+        );
+
+        for PoolSwapPair { pool, swap } in
+            batch::load_pool_swap_pairs(&ctx.accounts.pool_manager, ctx.remaining_accounts)?
+        {
+            let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(pool);
+            let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
+                &pool,
+                swap.to_account_info(),
+                ctx.accounts.swap_program.to_account_info(),
+            )
+            .with_signer(seeds);
+            stable_swap_anchor::set_new_fees(cpi_ctx, new_fees.into())?;
+        }
+        Ok(())
+    }
+
+    /// Ramps the amplification coefficient of many swaps to the same target at once.
+    /// The `(pool, swap)` pairs must be passed via `remaining_accounts`.
+    #[access_control(ctx.accounts.validate_role(roles::RAMPER))]
+    pub fn ramp_a_batch<'info>(
+        ctx: Context<'_, '_, '_, 'info, BatchSwapContext<'info>>,
+        target_amp: u64,
+        stop_ramp_ts: i64,
+    ) -> Result<()> {
+        invariant!(!ctx.accounts.pool_manager.is_timelocked(), TimelockRequired);
+        invariant!(
+            !ctx.accounts.pool_manager.is_locked(params::POOL_RAMPS),
+            ParamLocked
+        );
+
+        for PoolSwapPair { mut pool, swap } in
+            batch::load_pool_swap_pairs(&ctx.accounts.pool_manager, ctx.remaining_accounts)?
+        {
+            amp::record_ramp(
+                &ctx.accounts.pool_manager.ramp_policy,
+                &mut pool,
+                &swap,
+                target_amp,
+                stop_ramp_ts,
+            )?;
+            pool.exit(&crate::ID)?;
+
+            let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(pool);
+            let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
+                &pool,
+                swap.to_account_info(),
+                ctx.accounts.swap_program.to_account_info(),
+            )
+            .with_signer(seeds);
+            stable_swap_anchor::ramp_a(cpi_ctx, target_amp, stop_ramp_ts)?;
+        }
+        Ok(())
+    }
+
+    /// Schedules new fees to be set on the [SwapInfo] at `effective_ts`.
+    /// The fees may be applied by anyone via [pools::apply_scheduled_fees] once
+    /// `effective_ts` has passed.
+    #[access_control(ctx.accounts.validate())]
+    pub fn schedule_new_fees(
+        ctx: Context<ScheduleNewFees>,
+        new_fees: SwapFees,
+        effective_ts: i64,
+    ) -> Result<()> {
+        let pool_manager = &ctx.accounts.pool_manager;
+        invariant!(!pool_manager.is_locked(params::POOL_FEES), ParamLocked);
+        // fee changes may not be scheduled sooner than the timelock allows
+        let notice_period = pool_manager
+            .min_fee_notice_period
+            .max(pool_manager.timelock_delay);
+        let now = Clock::get()?.unix_timestamp;
+        let earliest_ts = unwrap_int!(now.checked_add(notice_period));
+        invariant!(effective_ts >= earliest_ts, FeeNoticePeriodTooShort);
+        invariant!(
+            ctx.accounts.pool_manager.fee_caps.allows(&new_fees),
+            FeesExceedCaps
+        );
+
+        let pool = &mut ctx.accounts.pool;
+        pool.scheduled_fees = new_fees;
+        pool.scheduled_fees_effective_ts = effective_ts;
+
+        Ok(())
+    }
+
+    /// Cancels the fees scheduled via [pools::schedule_new_fees].
+    #[access_control(ctx.accounts.validate())]
+    pub fn cancel_scheduled_fees(ctx: Context<ScheduleNewFees>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        invariant!(pool.has_scheduled_fees(), NoScheduledFees);
+        pool.scheduled_fees = SwapFees::default();
+        pool.scheduled_fees_effective_ts = 0;
+
+        Ok(())
+    }
+
+    /// Sets the fees scheduled via [pools::schedule_new_fees] on the [SwapInfo].
+    /// Anyone may call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn apply_scheduled_fees(ctx: Context<ApplyScheduledFees>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        let new_fees = pool.scheduled_fees;
+        // the caps may have been lowered since the fees were scheduled
+        invariant!(
+            ctx.accounts.pool_manager.fee_caps.allows(&new_fees),
+            FeesExceedCaps
+        );
+        pool.scheduled_fees = SwapFees::default();
+        pool.scheduled_fees_effective_ts = 0;
+
+        let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(ctx.accounts.pool);
+        let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
+            &ctx.accounts.pool,
+            ctx.accounts.swap.to_account_info(),
+            ctx.accounts.swap_program.to_account_info(),
+        )
+        .with_signer(seeds);
+        stable_swap_anchor::set_new_fees(cpi_ctx, new_fees.into())
+    }
+
+    /// Sets the [PoolManager::min_fee_notice_period].
+    /// Raising the notice period takes effect immediately. Lowering it only commits the new
+    /// period, which may be applied via [pools::apply_min_fee_notice_period] once the current
+    /// notice period has passed.
+    /// Before the first [Pool] is created there are no fees to give notice of,
+    /// so the notice period may then be lowered immediately.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_min_fee_notice_period(
+        ctx: Context<PoolManagerContext>,
+        min_fee_notice_period: i64,
+    ) -> Result<()> {
+        invariant!(min_fee_notice_period >= 0, InvalidNoticePeriod);
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        if min_fee_notice_period >= pool_manager.min_fee_notice_period
+            || pool_manager.num_pools == 0
+        {
+            pool_manager.min_fee_notice_period = min_fee_notice_period;
+            pool_manager.pending_min_fee_notice_period = 0;
+            pool_manager.pending_min_fee_notice_period_effective_ts = 0;
+        } else {
+            let now = Clock::get()?.unix_timestamp;
+            pool_manager.pending_min_fee_notice_period = min_fee_notice_period;
+            pool_manager.pending_min_fee_notice_period_effective_ts =
+                unwrap_int!(now.checked_add(pool_manager.min_fee_notice_period));
+        }
+
+        Ok(())
+    }
+
+    /// Sets the [PoolManager::swap_admin_change_delay].
+    /// The delay may always be raised, but only lowered before the first [Pool] is created,
+    /// since it protects the swaps of existing pools.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_swap_admin_change_delay(
+        ctx: Context<PoolManagerContext>,
+        swap_admin_change_delay: i64,
+    ) -> Result<()> {
+        invariant!(swap_admin_change_delay >= 0, InvalidSwapAdminChangeDelay);
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        invariant!(
+            swap_admin_change_delay >= pool_manager.swap_admin_change_delay
+                || pool_manager.num_pools == 0,
+            SwapAdminChangeDelayTooShort
+        );
+        pool_manager.swap_admin_change_delay = swap_admin_change_delay;
+
+        Ok(())
+    }
+
+    /// Applies the [PoolManager::pending_min_fee_notice_period].
+    #[access_control(ctx.accounts.validate())]
+    pub fn apply_min_fee_notice_period(ctx: Context<PoolManagerContext>) -> Result<()> {
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        invariant!(
+            pool_manager.pending_min_fee_notice_period_effective_ts != 0,
+            NoPendingNoticePeriod
+        );
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(
+            now >= pool_manager.pending_min_fee_notice_period_effective_ts,
+            PendingNoticePeriodNotEffective
+        );
+
+        pool_manager.min_fee_notice_period = pool_manager.pending_min_fee_notice_period;
+        pool_manager.pending_min_fee_notice_period = 0;
+        pool_manager.pending_min_fee_notice_period_effective_ts = 0;
+
+        Ok(())
+    }
+
+    /// Commits a new [PoolManager::ramp_policy].
+    /// This may be applied via [pools::apply_new_ramp_policy] after [RAMP_POLICY_CHANGE_DELAY].
+    /// Before the first [Pool] is created there are no ramps to protect,
+    /// so the ramp policy then takes effect immediately.
+    #[access_control(ctx.accounts.validate())]
+    pub fn commit_new_ramp_policy(
+        ctx: Context<PoolManagerContext>,
+        ramp_policy: RampPolicy,
+    ) -> Result<()> {
+        invariant!(ramp_policy.is_valid(), InvalidRampPolicy);
+        invariant!(
+            !ctx.accounts.pool_manager.is_locked(params::POOL_RAMPS),
+            ParamLocked
+        );
+
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        if pool_manager.num_pools == 0 {
+            pool_manager.ramp_policy = ramp_policy;
+            pool_manager.pending_ramp_policy = RampPolicy::default();
+            pool_manager.pending_ramp_policy_effective_ts = 0;
+        } else {
+            let now = Clock::get()?.unix_timestamp;
+            pool_manager.pending_ramp_policy = ramp_policy;
+            pool_manager.pending_ramp_policy_effective_ts =
+                unwrap_int!(now.checked_add(RAMP_POLICY_CHANGE_DELAY));
+        }
+
+        Ok(())
+    }
+
+    /// Applies the [PoolManager::pending_ramp_policy].
+    #[access_control(ctx.accounts.validate())]
+    pub fn apply_new_ramp_policy(ctx: Context<PoolManagerContext>) -> Result<()> {
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        // the ramps may have been locked since the ramp policy was committed
+        invariant!(!pool_manager.is_locked(params::POOL_RAMPS), ParamLocked);
+        invariant!(
+            pool_manager.pending_ramp_policy_effective_ts != 0,
+            NoPendingRampPolicy
+        );
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(
+            now >= pool_manager.pending_ramp_policy_effective_ts,
+            PendingRampPolicyNotEffective
+        );
+
+        pool_manager.ramp_policy = pool_manager.pending_ramp_policy;
+        pool_manager.pending_ramp_policy = RampPolicy::default();
+        pool_manager.pending_ramp_policy_effective_ts = 0;
+
+        Ok(())
+    }
+
+    /// Sets the [PoolManager::max_imbalance_bps].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_max_imbalance_bps(
+        ctx: Context<PoolManagerContext>,
+        max_imbalance_bps: u16,
+    ) -> Result<()> {
+        invariant!(max_imbalance_bps <= 10_000, InvalidImbalanceThreshold);
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        pool_manager.max_imbalance_bps = max_imbalance_bps;
+
+        Ok(())
+    }
+
+    /// Sets the [PoolManager::import_checks] required of permissionlessly imported pools.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_import_checks(ctx: Context<PoolManagerContext>, checks: u16) -> Result<()> {
+        invariant!(import_checks::are_valid(checks), InvalidImportChecks);
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        pool_manager.import_checks = checks;
+
+        Ok(())
+    }
+
+    /// Sets the [PoolManager::initial_fees] required of permissionlessly imported pools.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_initial_fees(
+        ctx: Context<PoolManagerContext>,
+        initial_fees: SwapFees,
+    ) -> Result<()> {
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        invariant!(!pool_manager.is_locked(params::INITIAL_FEES), ParamLocked);
+        invariant!(pool_manager.fee_caps.allows(&initial_fees), FeesExceedCaps);
+        pool_manager.initial_fees = initial_fees;
+
+        Ok(())
+    }
+
+    /// Sets the amp factor bounds of permissionlessly imported pools.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_permissionless_amp_bounds(
+        ctx: Context<PoolManagerContext>,
+        min_amp_factor: u64,
+        max_amp_factor: u64,
+    ) -> Result<()> {
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        invariant!(!pool_manager.is_locked(params::AMP_BOUNDS), ParamLocked);
+        invariant!(
+            min_amp_factor > 0 && min_amp_factor <= max_amp_factor,
+            InvalidAmpBounds
+        );
+        pool_manager.min_permissionless_amp_factor = min_amp_factor;
+        pool_manager.max_permissionless_amp_factor = max_amp_factor;
+
+        Ok(())
+    }
+
+    /// Locks [params] of the [PoolManager] so that they may never be changed again.
+    /// Locked parameters can never be unlocked.
+    #[access_control(ctx.accounts.validate())]
+    pub fn lock_params(ctx: Context<PoolManagerContext>, locked_params: u16) -> Result<()> {
+        invariant!(params::are_valid(locked_params), InvalidParams);
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        pool_manager.locked_params |= locked_params;
+
+        Ok(())
+    }
+
+    /// Permanently gives up the admin of the [PoolManager] by setting it to the default [Pubkey].
+    /// [Proposal]s may no longer act as the admin,
+    /// and admin-only actions can never be performed again.
+    /// [RoleGrant]s, [Pool::pool_admin]s, the operator and the guardian become inactive too.
+    /// Once [PoolManager::approvals_required] is set, this itself requires a [Proposal].
+    #[access_control(ctx.accounts.validate())]
+    pub fn renounce_admin(ctx: Context<PoolManagerContext>) -> Result<()> {
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        pool_manager.admin = Pubkey::default();
+        pool_manager.pending_admin = Pubkey::default();
+
+        Ok(())
+    }
+
+    /// Commits new [PoolManager::fee_caps].
+    /// These may be applied via [pools::apply_new_fee_caps] after [FEE_CAPS_CHANGE_DELAY].
+    #[access_control(ctx.accounts.validate())]
+    pub fn commit_new_fee_caps(ctx: Context<PoolManagerContext>, fee_caps: FeeCaps) -> Result<()> {
+        invariant!(fee_caps.is_valid(), InvalidFeeCaps);
+
+        let now = Clock::get()?.unix_timestamp;
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        pool_manager.pending_fee_caps = fee_caps;
+        pool_manager.pending_fee_caps_effective_ts =
+            unwrap_int!(now.checked_add(FEE_CAPS_CHANGE_DELAY));
+
+        Ok(())
+    }
+
+    /// Applies the [PoolManager::pending_fee_caps].
+    #[access_control(ctx.accounts.validate())]
+    pub fn apply_new_fee_caps(ctx: Context<PoolManagerContext>) -> Result<()> {
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        invariant!(
+            pool_manager.pending_fee_caps_effective_ts != 0,
+            NoPendingFeeCaps
+        );
+        let now = Clock::get()?.unix_timestamp;
+        invariant!(
+            now >= pool_manager.pending_fee_caps_effective_ts,
+            PendingFeeCapsNotEffective
+        );
+
+        pool_manager.fee_caps = pool_manager.pending_fee_caps;
+        pool_manager.pending_fee_caps = FeeCaps::default();
+        pool_manager.pending_fee_caps_effective_ts = 0;
+
+        Ok(())
+    }
+
+    /// Sets the [PoolManager::timelock_delay].
+    /// Once the timelock is enabled, the delay may only be changed through the timelock.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_timelock_delay(ctx: Context<PoolManagerContext>, timelock_delay: i64) -> Result<()> {
+        invariant!(!ctx.accounts.pool_manager.is_timelocked(), TimelockRequired);
+        invariant!(timelock_delay >= 0, InvalidTimelockDelay);
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        pool_manager.timelock_delay = timelock_delay;
+
+        Ok(())
+    }
+
+    /// Queues a [TimelockedAction] which may be executed by anyone
+    /// once [PoolManager::timelock_delay] has passed, until [TIMELOCK_GRACE_PERIOD] later.
+    #[access_control(ctx.accounts.validate())]
+    pub fn queue_timelocked_action(
+        ctx: Context<QueueTimelockedAction>,
+        action: TimelockedActionKind,
+    ) -> Result<()> {
+        invariant!(
+            !ctx.accounts.pool_manager.is_locked(action.params()),
+            ParamLocked
+        );
+        match action {
+            TimelockedActionKind::SetNewFees { new_fees, .. } => {
+                invariant!(
+                    ctx.accounts.pool_manager.fee_caps.allows(&new_fees),
+                    FeesExceedCaps
+                );
+                // the timelock must give at least as much notice as scheduling the fees
+                invariant!(
+                    ctx.accounts.pool_manager.timelock_delay
+                        >= ctx.accounts.pool_manager.min_fee_notice_period,
+                    FeeNoticePeriodTooShort
+                );
+            }
+            TimelockedActionKind::SetTimelockDelay { timelock_delay } => {
+                invariant!(timelock_delay >= 0, InvalidTimelockDelay);
+            }
+            _ => {}
+        }
+
+        let now = Clock::get()?.unix_timestamp;
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        let timelocked_action = &mut ctx.accounts.timelocked_action;
+        timelocked_action.manager = pool_manager.key();
+        timelocked_action.index = pool_manager.num_timelocked_actions;
+        timelocked_action.bump = unwrap_bump!(ctx, "timelocked_action");
+
+        timelocked_action.proposer = ctx.accounts.admin.key();
+        timelocked_action.action = action;
+
+        timelocked_action.eta = unwrap_int!(now.checked_add(pool_manager.timelock_delay));
+        timelocked_action.executed_at = 0;
+        timelocked_action.canceled_at = 0;
+
+        pool_manager.num_timelocked_actions =
+            unwrap_int!(pool_manager.num_timelocked_actions.checked_add(1));
+
+        Ok(())
+    }
+
+    /// Executes a [TimelockedAction] which modifies the [PoolManager].
+    /// A new operator or beneficiary is only committed and must still accept.
+    /// Anyone may call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn execute_timelocked_manager_action(
+        ctx: Context<ExecuteTimelockedManagerAction>,
+    ) -> Result<()> {
+        let timelocked_action = &mut ctx.accounts.timelocked_action;
+        timelocked_action.executed_at = Clock::get()?.unix_timestamp;
+
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        match timelocked_action.action {
+            TimelockedActionKind::SetOperator {
+                operator,
+                valid_until,
+            } => {
+                pool_manager.pending_operator = operator;
+                pool_manager.pending_operator_valid_until = valid_until;
+            }
+            TimelockedActionKind::SetBeneficiary { beneficiary } => {
+                pool_manager.pending_beneficiary = beneficiary;
+            }
+            TimelockedActionKind::SetTimelockDelay { timelock_delay } => {
+                pool_manager.timelock_delay = timelock_delay;
+            }
+            _ => return err!(TimelockedActionKindMismatch),
+        }
+
+        Ok(())
+    }
+
+    /// Executes a [TimelockedAction] which modifies the [SwapInfo] of a [Pool].
+    /// [TimelockedActionKind::CommitNewAdmin] only commits the new admin to the [Pool];
+    /// it must still be applied via [pools::apply_new_admin].
+    /// Anyone may call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn execute_timelocked_swap_action<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteTimelockedSwapAction<'info>>,
+    ) -> Result<()> {
+        let timelocked_action = &mut ctx.accounts.timelocked_action;
+        timelocked_action.executed_at = Clock::get()?.unix_timestamp;
+        let action = timelocked_action.action;
+
+        let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(ctx.accounts.pool);
+        match action {
+            TimelockedActionKind::SetNewFees { new_fees, .. } => {
+                // the caps may have been lowered since the action was queued
+                invariant!(
+                    ctx.accounts.pool_manager.fee_caps.allows(&new_fees),
+                    FeesExceedCaps
+                );
+                let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
+                    &ctx.accounts.pool,
+                    ctx.accounts.swap.to_account_info(),
+                    ctx.accounts.swap_program.to_account_info(),
+                )
+                .with_signer(seeds);
+                stable_swap_anchor::set_new_fees(cpi_ctx, new_fees.into())
+            }
+            TimelockedActionKind::RampA {
+                target_amp,
+                stop_ramp_ts,
+                ..
+            } => {
+                amp::record_ramp(
+                    &ctx.accounts.pool_manager.ramp_policy,
+                    &mut ctx.accounts.pool,
+                    &ctx.accounts.swap,
+                    target_amp,
+                    stop_ramp_ts,
+                )?;
+                let cpi_ctx = cpi_helpers::pool_admin_cpi_context(
+                    &ctx.accounts.pool,
+                    ctx.accounts.swap.to_account_info(),
+                    ctx.accounts.swap_program.to_account_info(),
+                )
+                .with_signer(seeds);
+                stable_swap_anchor::ramp_a(cpi_ctx, target_amp, stop_ramp_ts)
+            }
+            TimelockedActionKind::CommitNewAdmin { new_admin, .. } => {
+                // the time spent in the timelock counts towards the swap admin change delay
+                let now = Clock::get()?.unix_timestamp;
+                let remaining_delay = ctx
+                    .accounts
+                    .pool_manager
+                    .swap_admin_change_delay
+                    .saturating_sub(ctx.accounts.pool_manager.timelock_delay)
+                    .max(0);
+                let pool = &mut ctx.accounts.pool;
+                pool.pending_swap_admin = new_admin;
+                pool.pending_swap_admin_effective_ts =
+                    unwrap_int!(now.checked_add(remaining_delay));
+                Ok(())
+            }
+            _ => err!(TimelockedActionKindMismatch),
+        }
+    }
+
+    /// Cancels a [TimelockedAction].
+    /// This may be called by the admin or the guardian.
+    #[access_control(ctx.accounts.validate())]
+    pub fn cancel_timelocked_action(ctx: Context<CancelTimelockedAction>) -> Result<()> {
+        let timelocked_action = &mut ctx.accounts.timelocked_action;
+        timelocked_action.canceled_at = Clock::get()?.unix_timestamp;
+
+        Ok(())
+    }
+
+    /// Enables built-in M-of-N approvals on the [PoolManager] by creating its [ApprovalConfig].
+    /// From then on, only executed [Proposal]s may act as the admin.
+    /// This cannot be undone.
+    #[access_control(ctx.accounts.validate())]
+    pub fn enable_approvals(
+        ctx: Context<EnableApprovals>,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        validate_approvers(&approvers, threshold)?;
+
+        let approval_config = &mut ctx.accounts.approval_config;
+        approval_config.manager = ctx.accounts.pool_manager.key();
+        approval_config.bump = unwrap_bump!(ctx, "approval_config");
+
+        approval_config.threshold = threshold;
+        approval_config.approver_set_seqno = 0;
+        approval_config.num_proposals = 0;
+        approval_config.approvers = approvers;
+
+        ctx.accounts.pool_manager.approvals_required = true;
+
+        Ok(())
+    }
+
+    /// Sets the approvers and threshold of the [ApprovalConfig].
+    /// This invalidates all pending [Proposal]s.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_approvers(
+        ctx: Context<SetApprovers>,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        validate_approvers(&approvers, threshold)?;
+
+        let approval_config = &mut ctx.accounts.approval_config;
+        approval_config.threshold = threshold;
+        approval_config.approver_set_seqno =
+            unwrap_int!(approval_config.approver_set_seqno.checked_add(1));
+        approval_config.approvers = approvers;
+
+        Ok(())
+    }
+
+    /// Creates a [Proposal] to execute an instruction as the admin.
+    /// The proposer must be an approver, and approves the [Proposal].
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_proposal(
+        ctx: Context<CreateProposal>,
+        instruction: ProposalInstruction,
+    ) -> Result<()> {
+        invariant!(!instruction.data.is_empty(), EmptyProposal);
+
+        let approval_config = &mut ctx.accounts.approval_config;
+        let proposer_index = unwrap_opt!(
+            approval_config.approver_index(&ctx.accounts.proposer.key()),
+            NotApprover
+        );
+
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.manager = approval_config.manager;
+        proposal.index = approval_config.num_proposals;
+        proposal.bump = unwrap_bump!(ctx, "proposal");
+
+        proposal.proposer = ctx.accounts.proposer.key();
+        proposal.approver_set_seqno = approval_config.approver_set_seqno;
+        proposal.approvals = 1 << proposer_index;
+
+        proposal.instruction = instruction;
+
+        proposal.created_at = Clock::get()?.unix_timestamp;
+        proposal.executed_at = 0;
+
+        approval_config.num_proposals = unwrap_int!(approval_config.num_proposals.checked_add(1));
+
+        Ok(())
+    }
+
+    /// Approves a [Proposal].
+    #[access_control(ctx.accounts.validate())]
+    pub fn approve_proposal(ctx: Context<ApproveProposal>) -> Result<()> {
+        let approver_index = unwrap_opt!(
+            ctx.accounts
+                .approval_config
+                .approver_index(&ctx.accounts.approver.key()),
+            NotApprover
+        );
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.approvals |= 1 << approver_index;
+
+        Ok(())
+    }
+
+    /// Executes a [Proposal] which has enough approvals, signing as the [Proposal].
+    /// The accounts of the [ProposalInstruction] must be passed via `remaining_accounts`.
+    /// Anyone may call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn execute_proposal<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteProposal<'info>>,
+    ) -> Result<()> {
+        let proposal = &mut ctx.accounts.proposal;
+        proposal.executed_at = Clock::get()?.unix_timestamp;
+        // persist the execution before invoking, so the proposal cannot be re-entered
+        proposal.exit(&crate::ID)?;
+
+        let instruction = Instruction {
+            program_id: crate::ID,
+            accounts: proposal.instruction.keys.iter().map(Into::into).collect(),
+            data: proposal.instruction.data.clone(),
+        };
+        let mut account_infos = ctx.remaining_accounts.to_vec();
+        account_infos.push(proposal.to_account_info());
+
+        let seeds: &[&[&[u8]]] = &[&[
+            b"SaberProposal".as_ref(),
+            &proposal.manager.to_bytes(),
+            &proposal.index.to_le_bytes(),
+            &[proposal.bump],
+        ]];
+        invoke_signed(&instruction, &account_infos, seeds)?;
+
+        Ok(())
+    }
+
+    /// Sends fees on a [Pool] fee account to an ATA controlled by the beneficiary.
+    /// Anyone may call this.
+    #[access_control(ctx.accounts.validate())]
+    pub fn send_fees_to_beneficiary(ctx: Context<SendFeesToBeneficiary>) -> Result<()> {
+        let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(ctx.accounts.pool);
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.fee_account.to_account_info(),
+                    to: ctx.accounts.beneficiary_account.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+            )
+            .with_signer(seeds),
+            ctx.accounts.fee_account.amount,
+        )
+    }
+
+    /// Sends tokens mistakenly sent to a token account owned by a [Pool]
+    /// to an ATA controlled by the beneficiary.
+    /// The fee accounts and swap reserves may not be recovered.
+    #[access_control(ctx.accounts.validate())]
+    pub fn recover_tokens(ctx: Context<RecoverTokens>) -> Result<()> {
+        let seeds: &[&[&[u8]]] = gen_pool_signer_seeds!(ctx.accounts.pool);
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                token::Transfer {
+                    from: ctx.accounts.token_account.to_account_info(),
+                    to: ctx.accounts.beneficiary_account.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+            )
+            .with_signer(seeds),
+            ctx.accounts.token_account.amount,
+        )
+    }
+
+    /// Sends lamports above the rent-exempt minimum of a [Pool] to the beneficiary.
+    #[access_control(ctx.accounts.validate())]
+    pub fn recover_lamports(ctx: Context<RecoverLamports>) -> Result<()> {
+        let pool_info = ctx.accounts.pool.to_account_info();
+        let rent_exempt_minimum = Rent::get()?.minimum_balance(pool_info.data_len());
+        let amount = unwrap_int!(pool_info.lamports().checked_sub(rent_exempt_minimum));
+
+        let beneficiary_info = ctx.accounts.beneficiary.to_account_info();
+        **pool_info.try_borrow_mut_lamports()? = rent_exempt_minimum;
+        **beneficiary_info.try_borrow_mut_lamports()? =
+            unwrap_int!(beneficiary_info.lamports().checked_add(amount));
+
+        Ok(())
+    }
+
+    /// Sets the [PoolManager::operator], who may act until `valid_until`.
+    /// Unless `force` is set, this only commits the new operator,
+    /// who must then accept via [pools::accept_operator].
+    /// Forcing should only be used in emergencies.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_operator(ctx: Context<SetOperator>, valid_until: i64, force: bool) -> Result<()> {
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        if force {
+            pool_manager.operator = ctx.accounts.operator.key();
+            pool_manager.operator_valid_until = valid_until;
+            pool_manager.pending_operator = Pubkey::default();
+            pool_manager.pending_operator_valid_until = 0;
+        } else {
+            pool_manager.pending_operator = ctx.accounts.operator.key();
+            pool_manager.pending_operator_valid_until = valid_until;
+        }
+
+        Ok(())
+    }
+
+    /// Accepts becoming the [PoolManager::operator].
+    #[access_control(ctx.accounts.validate())]
+    pub fn accept_operator(ctx: Context<AcceptOperator>) -> Result<()> {
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        pool_manager.operator = pool_manager.pending_operator;
+        pool_manager.operator_valid_until = pool_manager.pending_operator_valid_until;
+        pool_manager.pending_operator = Pubkey::default();
+        pool_manager.pending_operator_valid_until = 0;
+
+        Ok(())
+    }
+
+    /// Sets the [PoolManager::beneficiary].
+    /// Unless `force` is set, this only commits the new beneficiary,
+    /// who must then accept via [pools::accept_beneficiary].
+    /// Forcing should only be used in emergencies.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_beneficiary(ctx: Context<SetBeneficiary>, force: bool) -> Result<()> {
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        if force {
+            pool_manager.beneficiary = ctx.accounts.beneficiary.key();
+            pool_manager.pending_beneficiary = Pubkey::default();
+        } else {
+            pool_manager.pending_beneficiary = ctx.accounts.beneficiary.key();
+        }
+
+        Ok(())
+    }
+
+    /// Accepts becoming the [PoolManager::beneficiary].
+    #[access_control(ctx.accounts.validate())]
+    pub fn accept_beneficiary(ctx: Context<AcceptBeneficiary>) -> Result<()> {
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        pool_manager.beneficiary = pool_manager.pending_beneficiary;
+        pool_manager.pending_beneficiary = Pubkey::default();
+
+        Ok(())
+    }
+
+    /// Creates a [RoleGrant] giving roles on the [PoolManager] to the grantee until `valid_until`.
+    #[access_control(ctx.accounts.validate())]
+    pub fn create_role_grant(
+        ctx: Context<CreateRoleGrant>,
+        roles: u16,
+        valid_until: i64,
+    ) -> Result<()> {
+        invariant!(roles::are_valid(roles), InvalidRoles);
+
+        let role_grant = &mut ctx.accounts.role_grant;
+        role_grant.manager = ctx.accounts.pool_manager.key();
+        role_grant.grantee = ctx.accounts.grantee.key();
+        role_grant.bump = unwrap_bump!(ctx, "role_grant");
+        role_grant.roles = roles;
+        role_grant.valid_until = valid_until;
+
+        Ok(())
+    }
+
+    /// Sets the roles of a [RoleGrant] and when they expire.
+    /// Setting no roles revokes the grant.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_roles(ctx: Context<SetRoles>, roles: u16, valid_until: i64) -> Result<()> {
+        invariant!(roles::are_valid(roles), InvalidRoles);
+
+        let role_grant = &mut ctx.accounts.role_grant;
+        role_grant.roles = roles;
+        role_grant.valid_until = valid_until;
+
+        Ok(())
+    }
+
+    /// Sets the [Pool::pool_admin], who may ramp, pause and set fees on the [Pool]
+    /// within the limits of the [PoolManager].
+    /// Setting the default [Pubkey] removes the delegate.
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_pool_admin(ctx: Context<SetPoolAdmin>) -> Result<()> {
+        let pool = &mut ctx.accounts.pool;
+        pool.pool_admin = ctx.accounts.pool_admin.key();
+
+        Ok(())
+    }
+
+    /// Enables or disables importing pools.
+    /// The admin may set the flags either way, but the guardian may only disable imports.
+    #[access_control(ctx.accounts.validate_flags(
+        permissionless_imports_enabled,
+        operator_imports_enabled
+    ))]
+    pub fn set_imports_enabled(
+        ctx: Context<SetImportsEnabled>,
+        permissionless_imports_enabled: bool,
+        operator_imports_enabled: bool,
+    ) -> Result<()> {
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        pool_manager.permissionless_imports_enabled = permissionless_imports_enabled;
+        pool_manager.operator_imports_enabled = operator_imports_enabled;
+
+        Ok(())
+    }
+
+    /// Sets the [PoolManager::guardian].
+    #[access_control(ctx.accounts.validate())]
+    pub fn set_guardian(ctx: Context<SetGuardian>) -> Result<()> {
+        let pool_manager = &mut ctx.accounts.pool_manager;
+        pool_manager.guardian = ctx.accounts.guardian.key();
+
+        Ok(())
+    }
+
+    /// Resizes a [PoolManager] to the current layout and fills new fields with defaults.
+    /// Anyone may call this; the payer funds any additional rent.
+    /// All other instructions reject a [PoolManager] until it has been migrated.
+    pub fn migrate_pool_manager(ctx: Context<MigratePoolManager>) -> Result<()> {
+        let info = ctx.accounts.pool_manager.to_account_info();
+        migrate::realloc_account(
+            &info,
+            PoolManager::discriminator(),
+            8 + PoolManager::LEN,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+        )?;
+
+        let mut pool_manager = Account::<PoolManager>::try_from(&info)?;
+        invariant!(
+            pool_manager.version < PoolManager::VERSION,
+            AccountAlreadyMigrated
+        );
+        pool_manager.migrate();
+        pool_manager.exit(&crate::ID)?;
+
+        Ok(())
+    }
+
+    /// Resizes a [Pool] to the current layout and fills new fields with defaults.
+    /// Anyone may call this; the payer funds any additional rent.
+    /// All other instructions reject a [Pool] until it has been migrated.
+    pub fn migrate_pool(ctx: Context<MigratePool>) -> Result<()> {
+        let info = ctx.accounts.pool.to_account_info();
+        migrate::realloc_account(
+            &info,
+            Pool::discriminator(),
+            8 + Pool::LEN,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+        )?;
+
+        let mut pool = Account::<Pool>::try_from(&info)?;
+        invariant!(pool.version < Pool::VERSION, AccountAlreadyMigrated);
+        pool.migrate();
+        pool.exit(&crate::ID)?;
+
+        Ok(())
+    }
+}
+
+/// Checks that a set of approvers and threshold may be used in an [ApprovalConfig].
+fn validate_approvers(approvers: &[Pubkey], threshold: u8) -> Result<()> {
+    invariant!(
+        !approvers.is_empty() && approvers.len() <= MAX_APPROVERS,
+        InvalidApprovers
+    );
+    invariant!(
+        threshold >= 1 && usize::from(threshold) <= approvers.len(),
+        InvalidApprovers
+    );
+    for (i, approver) in approvers.iter().enumerate() {
+        invariant!(!approvers[..i].contains(approver), InvalidApprovers);
+    }
+    Ok(())
+}
+
+/// Checks that the steps may be used in an [AmpSchedule].
+fn validate_amp_schedule_steps(steps: &[AmpScheduleStep]) -> Result<()> {
+    invariant!(steps.len() <= MAX_AMP_SCHEDULE_STEPS, InvalidAmpSchedule);
+    for (i, step) in steps.iter().enumerate() {
+        invariant!(step.target_amp > 0, InvalidAmpSchedule);
+        if i > 0 {
+            invariant!(
+                step.stop_ramp_ts > steps[i - 1].stop_ramp_ts,
+                InvalidAmpSchedule
+            );
+        }
+    }
+    Ok(())
+}
+
+/// Accounts for [pools::new_pool_manager].
+#[derive(Accounts)]
+pub struct NewPoolManager<'info> {
+    /// The [PoolManager].
+    #[account(
+        init,
+        seeds = [
+            b"SaberPoolManager".as_ref(),
+            base.key().to_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + PoolManager::LEN,
+        payer = payer
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+
+    /// Base key.
+    pub base: Signer<'info>,
+
+    /// Initial admin of the [PoolManager].
+    /// CHECK: Initializer.
+    pub admin: UncheckedAccount<'info>,
+
+    /// Initial operator of the [PoolManager].
+    /// CHECK: Initializer.
+    pub operator: UncheckedAccount<'info>,
+
+    /// Initial beneficiary of the [PoolManager].
+    /// CHECK: Initializer.
+    pub beneficiary: UncheckedAccount<'info>,
+
+    /// Payer of the [PoolManager] initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for [pools::import_pool_permissionless].
+#[derive(Accounts)]
+pub struct ImportPoolPermissionless<'info> {
+    /// The [PoolManager].
+    #[account(mut, constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Box<Account<'info, PoolManager>>,
+
+    /// [SwapInfo] to import.
+    pub swap: Box<Account<'info, SwapInfo>>,
+
+    /// [Pool].
+    #[account(
+        init,
+        seeds = [
+            b"SaberPool".as_ref(),
+            pool_manager.key().to_bytes().as_ref(),
+            swap.sorted_mints().0.to_bytes().as_ref(),
+            swap.sorted_mints().1.to_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + Pool::LEN,
+        payer = payer
+    )]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// Fee account for token A.
+    #[account(address = swap.token_a.admin_fees)]
+    pub token_a_fees: Box<Account<'info, TokenAccount>>,
+
+    /// Fee account for token B.
+    #[account(address = swap.token_b.admin_fees)]
+    pub token_b_fees: Box<Account<'info, TokenAccount>>,
+
+    /// Mint of the LP token.
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// Mint of token A.
+    #[account(address = swap.token_a.mint)]
+    pub token_a_mint: Box<Account<'info, Mint>>,
+    /// Mint of token B.
+    #[account(address = swap.token_b.mint)]
+    pub token_b_mint: Box<Account<'info, Mint>>,
+
+    /// Reserve of token A.
+    #[account(address = swap.token_a.reserves)]
+    pub token_a_reserve: Box<Account<'info, TokenAccount>>,
+    /// Reserve of token B.
+    #[account(address = swap.token_b.reserves)]
+    pub token_b_reserve: Box<Account<'info, TokenAccount>>,
+
+    /// Payer of the [Pool] initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for [pools::check_import].
+/// These are the same accounts as [ImportPoolPermissionless], but their relations
+/// are reported by the instruction rather than enforced.
+#[derive(Accounts)]
+pub struct CheckImport<'info> {
+    /// The [PoolManager].
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Box<Account<'info, PoolManager>>,
+    /// [SwapInfo] to import.
+    pub swap: Box<Account<'info, SwapInfo>>,
+    /// [Pool] which would be created.
+    /// CHECK: Checked by the instruction.
+    pub pool: UncheckedAccount<'info>,
+    /// Fee account for token A.
+    pub token_a_fees: Box<Account<'info, TokenAccount>>,
+    /// Fee account for token B.
+    pub token_b_fees: Box<Account<'info, TokenAccount>>,
+    /// Mint of the LP token.
+    pub lp_mint: Box<Account<'info, Mint>>,
+    /// Mint of token A.
+    pub token_a_mint: Box<Account<'info, Mint>>,
+    /// Mint of token B.
+    pub token_b_mint: Box<Account<'info, Mint>>,
+    /// Reserve of token A.
+    pub token_a_reserve: Box<Account<'info, TokenAccount>>,
+    /// Reserve of token B.
+    pub token_b_reserve: Box<Account<'info, TokenAccount>>,
+    /// Payer of the [Pool] initialization. Need not sign.
+    /// CHECK: Unused.
+    pub payer: UncheckedAccount<'info>,
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for [pools::adopt_pool].
+#[derive(Accounts)]
+pub struct AdoptPool<'info> {
+    /// Import pool accounts.
+    pub import_pool: ImportPoolPermissionless<'info>,
+    /// The [SwapInfo] to import, writable so that the new admin may be applied.
+    #[account(mut, address = import_pool.swap.key())]
+    pub swap: Account<'info, SwapInfo>,
+    /// The admin of the [SwapInfo] which committed the [Pool] as its new admin.
+    pub previous_admin: Signer<'info>,
+    pub swap_program: Program<'info, StableSwap>,
+}
+
+/// Accounts for [pools::import_pool_as_operator].
+#[derive(Accounts)]
+pub struct ImportPoolAsOperator<'info> {
+    /// The admin, operator, or a [roles::IMPORTER] of the [PoolManager].
+    pub authority: Signer<'info>,
+    /// The [RoleGrant] of the authority. Ignored if the authority is the admin or operator.
+    /// CHECK: Checked in validators.
+    pub role_grant: UncheckedAccount<'info>,
+    /// Import pool accounts.
+    pub import_pool: ImportPoolPermissionless<'info>,
+}
+
+/// Accounts for admin-related swap operations.
+#[derive(Accounts)]
+pub struct SwapContext<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(mut)]
+    pub swap: Account<'info, SwapInfo>,
+    #[account(
+        mut,
+        has_one = swap,
+        constraint = pool.manager == pool_manager.key(),
+        constraint = pool.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool: Account<'info, Pool>,
+    pub swap_program: Program<'info, StableSwap>,
+    /// The admin, an account granted the role required by the instruction,
+    /// or the [Pool::pool_admin].
+    pub authority: Signer<'info>,
+    /// The [RoleGrant] of the authority. Ignored if the authority is the admin.
+    /// CHECK: Checked in validators.
+    pub role_grant: UncheckedAccount<'info>,
+}
+
+/// Accounts for [pools::creator_ramp_a] and [pools::creator_stop_ramp_a].
+#[derive(Accounts)]
+pub struct CreatorSwapContext<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(mut)]
+    pub swap: Account<'info, SwapInfo>,
+    #[account(
+        mut,
+        has_one = swap,
+        constraint = pool.manager == pool_manager.key(),
+        constraint = pool.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool: Account<'info, Pool>,
+    pub swap_program: Program<'info, StableSwap>,
+    /// The [Pool::creator].
+    pub creator: Signer<'info>,
+}
+
+/// Accounts for [pools::create_amp_schedule].
+#[derive(Accounts)]
+pub struct CreateAmpSchedule<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(
+        constraint = pool.manager == pool_manager.key(),
+        constraint = pool.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool: Account<'info, Pool>,
+    /// The [AmpSchedule].
+    #[account(
+        init,
+        seeds = [
+            b"SaberAmpSchedule".as_ref(),
+            pool.key().to_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + AmpSchedule::LEN,
+        payer = payer
+    )]
+    pub amp_schedule: Account<'info, AmpSchedule>,
+    /// The admin or operator of the [PoolManager].
+    pub authority: Signer<'info>,
+    /// Payer of the [AmpSchedule] initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for [pools::set_amp_schedule].
+#[derive(Accounts)]
+pub struct SetAmpSchedule<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(
+        constraint = pool.manager == pool_manager.key(),
+        constraint = pool.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut, has_one = pool)]
+    pub amp_schedule: Account<'info, AmpSchedule>,
+    /// The admin or operator of the [PoolManager].
+    pub authority: Signer<'info>,
+}
+
+/// Accounts for [pools::advance_amp_schedule].
+#[derive(Accounts)]
+pub struct AdvanceAmpSchedule<'info> {
+    #[account(
+        address = pool.manager,
+        constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(mut)]
+    pub swap: Account<'info, SwapInfo>,
+    #[account(
+        mut,
+        has_one = swap,
+        constraint = pool.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool: Account<'info, Pool>,
+    #[account(mut, has_one = pool)]
+    pub amp_schedule: Account<'info, AmpSchedule>,
+    pub swap_program: Program<'info, StableSwap>,
+}
+
+/// Accounts for [pools::pause_swap].
+#[derive(Accounts)]
+pub struct PauseSwap<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(mut)]
+    pub swap: Account<'info, SwapInfo>,
+    #[account(
+        has_one = swap,
+        constraint = pool.manager == pool_manager.key(),
+        constraint = pool.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool: Account<'info, Pool>,
+    pub swap_program: Program<'info, StableSwap>,
+    /// The admin, guardian, [Pool::pool_admin], or a [roles::PAUSER] of the [PoolManager].
+    pub authority: Signer<'info>,
+    /// The [RoleGrant] of the authority. Ignored if the authority is the admin or guardian.
+    /// CHECK: Checked in validators.
+    pub role_grant: UncheckedAccount<'info>,
+}
+
+/// Accounts for [pools::trip_circuit_breaker].
+#[derive(Accounts)]
+pub struct TripCircuitBreaker<'info> {
+    #[account(
+        address = pool.manager,
+        constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(mut)]
+    pub swap: Account<'info, SwapInfo>,
+    #[account(
+        mut,
+        has_one = swap,
+        constraint = pool.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool: Account<'info, Pool>,
+    /// Reserve of token A of the swap.
+    pub reserve_a: Account<'info, TokenAccount>,
+    /// Reserve of token B of the swap.
+    pub reserve_b: Account<'info, TokenAccount>,
+    pub swap_program: Program<'info, StableSwap>,
+}
+
+/// Accounts for [pools::create_mint_oracle].
+#[derive(Accounts)]
+pub struct CreateMintOracle<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    pub mint: Account<'info, Mint>,
+    /// The [MintOracle].
+    #[account(
+        init,
+        seeds = [
+            b"SaberMintOracle".as_ref(),
+            pool_manager.key().to_bytes().as_ref(),
+            mint.key().to_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + MintOracle::LEN,
+        payer = payer
+    )]
+    pub mint_oracle: Account<'info, MintOracle>,
+    /// Pyth-style price account of the mint.
+    /// CHECK: Parsed in [pools::check_depeg].
+    pub price_account: UncheckedAccount<'info>,
+    pub admin: Signer<'info>,
+    /// Payer of the [MintOracle] initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for [pools::set_mint_oracle].
+#[derive(Accounts)]
+pub struct SetMintOracle<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(mut)]
+    pub mint_oracle: Account<'info, MintOracle>,
+    /// Pyth-style price account of the mint.
+    /// CHECK: Parsed in [pools::check_depeg].
+    pub price_account: UncheckedAccount<'info>,
+    pub admin: Signer<'info>,
+}
+
+/// Accounts for [pools::check_depeg].
+/// The `(pool, swap)` pairs are passed via `remaining_accounts`.
+#[derive(Accounts)]
+pub struct CheckDepeg<'info> {
+    #[account(
+        address = mint_oracle.manager,
+        constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+    pub mint_oracle: Account<'info, MintOracle>,
+    /// The [MintOracle::price_account].
+    /// CHECK: Checked in validators and parsed.
+    pub price_account: UncheckedAccount<'info>,
+    pub swap_program: Program<'info, StableSwap>,
+}
+
+/// Accounts for [pools::pause_by_mint].
+/// The `(pool, swap)` pairs are passed via `remaining_accounts`.
+#[derive(Accounts)]
+pub struct PauseByMint<'info> {
+    #[account(mut, constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    /// The [Mint] held by the swaps to pause.
+    pub mint: Account<'info, Mint>,
+    /// The [MintIncident].
+    #[account(
+        init,
+        seeds = [
+            b"SaberMintIncident".as_ref(),
+            pool_manager.key().to_bytes().as_ref(),
+            pool_manager.num_mint_incidents.to_le_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + MintIncident::LEN,
+        payer = payer
+    )]
+    pub mint_incident: Account<'info, MintIncident>,
+    /// The admin or guardian of the [PoolManager].
+    pub authority: Signer<'info>,
+    /// Payer of the [MintIncident] initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+    pub swap_program: Program<'info, StableSwap>,
+}
+
+/// Accounts for [pools::pause_swaps].
+/// The `(pool, swap)` pairs are passed via `remaining_accounts`.
+#[derive(Accounts)]
+pub struct BatchPauseSwaps<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    pub swap_program: Program<'info, StableSwap>,
+    /// The admin, guardian, or a [roles::PAUSER] of the [PoolManager].
+    pub authority: Signer<'info>,
+    /// The [RoleGrant] of the authority. Ignored if the authority is the admin or guardian.
+    /// CHECK: Checked in validators.
+    pub role_grant: UncheckedAccount<'info>,
+}
+
+/// Accounts for admin-related operations on many swaps at once.
+/// The `(pool, swap)` pairs are passed via `remaining_accounts`.
+#[derive(Accounts)]
+pub struct BatchSwapContext<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    pub swap_program: Program<'info, StableSwap>,
+    /// The admin, or an account granted the role required by the instruction.
+    pub authority: Signer<'info>,
+    /// The [RoleGrant] of the authority. Ignored if the authority is the admin.
+    /// CHECK: Checked in validators.
+    pub role_grant: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct CommitNewAdmin<'info> {
+    #[account(
+        address = pool.manager,
+        constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(mut)]
+    pub swap: Account<'info, SwapInfo>,
+    #[account(
+        mut,
+        has_one = swap,
+        constraint = pool.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool: Account<'info, Pool>,
+    pub admin: Signer<'info>,
+    /// CHECK: Arbitrary.
+    pub new_admin: UncheckedAccount<'info>,
+    pub swap_program: Program<'info, StableSwap>,
+}
+
+/// Accounts for [pools::schedule_new_fees] and [pools::cancel_scheduled_fees].
+#[derive(Accounts)]
+pub struct ScheduleNewFees<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(
+        mut,
+        constraint = pool.manager == pool_manager.key(),
+        constraint = pool.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool: Account<'info, Pool>,
+    /// The admin, [Pool::pool_admin], or a [roles::FEE_SETTER] of the [PoolManager].
+    pub authority: Signer<'info>,
+    /// The [RoleGrant] of the authority. Ignored if the authority is the admin.
+    /// CHECK: Checked in validators.
+    pub role_grant: UncheckedAccount<'info>,
+}
+
+/// Accounts for [pools::apply_scheduled_fees].
+#[derive(Accounts)]
+pub struct ApplyScheduledFees<'info> {
+    #[account(
+        address = pool.manager,
+        constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(mut)]
+    pub swap: Account<'info, SwapInfo>,
+    #[account(
+        mut,
+        has_one = swap,
+        constraint = pool.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool: Account<'info, Pool>,
+    pub swap_program: Program<'info, StableSwap>,
+}
 
 #[derive(Accounts)]
 pub struct SendFeesToBeneficiary<'info> {
-    #[account(address = pool.manager)]
+    #[account(
+        address = pool.manager,
+        constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
     pub pool_manager: Account<'info, PoolManager>,
+    #[account(constraint = pool.is_migrated() @ ErrorCode::AccountNeedsMigration)]
     pub pool: Account<'info, Pool>,
     #[account(
         mut,
@@ -366,31 +2143,347 @@ pub struct SendFeesToBeneficiary<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+/// Accounts for [pools::recover_tokens].
+#[derive(Accounts)]
+pub struct RecoverTokens<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(constraint = pool.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool: Account<'info, Pool>,
+    /// The [SwapInfo] of the [Pool], whose reserves may not be recovered.
+    pub swap: Account<'info, SwapInfo>,
+    /// The admin or a [roles::SWEEPER] of the [PoolManager].
+    pub authority: Signer<'info>,
+    /// The [RoleGrant] of the authority. Ignored if the authority is the admin.
+    /// CHECK: Checked in validators.
+    pub role_grant: UncheckedAccount<'info>,
+    /// Token account owned by the [Pool] to recover tokens from.
+    #[account(mut)]
+    pub token_account: Account<'info, TokenAccount>,
+    /// Token account owned by the [PoolManager::beneficiary].
+    #[account(mut)]
+    pub beneficiary_account: Account<'info, TokenAccount>,
+    pub token_program: Program<'info, Token>,
+}
+
+/// Accounts for [pools::recover_lamports].
+#[derive(Accounts)]
+pub struct RecoverLamports<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(mut, constraint = pool.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool: Account<'info, Pool>,
+    /// The admin or a [roles::SWEEPER] of the [PoolManager].
+    pub authority: Signer<'info>,
+    /// The [RoleGrant] of the authority. Ignored if the authority is the admin.
+    /// CHECK: Checked in validators.
+    pub role_grant: UncheckedAccount<'info>,
+    /// The [PoolManager::beneficiary].
+    /// CHECK: Checked in validators.
+    #[account(mut)]
+    pub beneficiary: UncheckedAccount<'info>,
+}
+
 #[derive(Accounts)]
 pub struct SetOperator<'info> {
-    #[account(mut, has_one = admin @ ErrorCode::NotAdmin)]
+    #[account(mut, constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
     pub pool_manager: Account<'info, PoolManager>,
     pub admin: Signer<'info>,
     /// CHECK: Arbitrary account.
     pub operator: UncheckedAccount<'info>,
 }
 
+/// Accounts for [pools::accept_operator].
+#[derive(Accounts)]
+pub struct AcceptOperator<'info> {
+    #[account(mut, constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    /// The [PoolManager::pending_operator].
+    pub operator: Signer<'info>,
+}
+
 #[derive(Accounts)]
 pub struct SetBeneficiary<'info> {
-    #[account(mut, has_one = admin @ ErrorCode::NotAdmin)]
+    #[account(mut, constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
     pub pool_manager: Account<'info, PoolManager>,
-    pub admin: Signer<'info>,
+    /// The admin or a [roles::BENEFICIARY_MANAGER] of the [PoolManager].
+    pub authority: Signer<'info>,
+    /// The [RoleGrant] of the authority. Ignored if the authority is the admin.
+    /// CHECK: Checked in validators.
+    pub role_grant: UncheckedAccount<'info>,
     /// The account which will be able to receive all admin fees accrued by pools.
     /// CHECK: Arbitrary account.
     pub beneficiary: UncheckedAccount<'info>,
 }
 
+/// Accounts for [pools::accept_beneficiary].
+#[derive(Accounts)]
+pub struct AcceptBeneficiary<'info> {
+    #[account(mut, constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    /// The [PoolManager::pending_beneficiary].
+    pub beneficiary: Signer<'info>,
+}
+
+/// Accounts for [pools::create_role_grant].
+#[derive(Accounts)]
+pub struct CreateRoleGrant<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    /// The [RoleGrant].
+    #[account(
+        init,
+        seeds = [
+            b"SaberRoleGrant".as_ref(),
+            pool_manager.key().to_bytes().as_ref(),
+            grantee.key().to_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + RoleGrant::LEN,
+        payer = payer
+    )]
+    pub role_grant: Account<'info, RoleGrant>,
+    /// The account receiving the roles.
+    /// CHECK: Arbitrary account.
+    pub grantee: UncheckedAccount<'info>,
+    pub admin: Signer<'info>,
+    /// Payer of the [RoleGrant] initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for [pools::set_roles].
+#[derive(Accounts)]
+pub struct SetRoles<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(mut)]
+    pub role_grant: Account<'info, RoleGrant>,
+    pub admin: Signer<'info>,
+}
+
+/// Accounts for [pools::set_pool_admin].
+#[derive(Accounts)]
+pub struct SetPoolAdmin<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(mut, constraint = pool.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool: Account<'info, Pool>,
+    pub admin: Signer<'info>,
+    /// The account which will be able to administer the [Pool].
+    /// CHECK: Arbitrary account.
+    pub pool_admin: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetGuardian<'info> {
+    #[account(mut, constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    pub admin: Signer<'info>,
+    /// The account which will be able to pause swaps.
+    /// CHECK: Arbitrary account.
+    pub guardian: UncheckedAccount<'info>,
+}
+
+/// Accounts for [pools::set_imports_enabled].
+#[derive(Accounts)]
+pub struct SetImportsEnabled<'info> {
+    #[account(mut, constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    /// The admin or guardian of the [PoolManager].
+    pub authority: Signer<'info>,
+}
+
+/// Accounts for admin-related [PoolManager] operations.
+#[derive(Accounts)]
+pub struct PoolManagerContext<'info> {
+    #[account(mut, constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    pub admin: Signer<'info>,
+}
+
+/// Accounts for [pools::queue_timelocked_action].
+#[derive(Accounts)]
+pub struct QueueTimelockedAction<'info> {
+    #[account(mut, constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    /// The [TimelockedAction].
+    #[account(
+        init,
+        seeds = [
+            b"SaberTimelockedAction".as_ref(),
+            pool_manager.key().to_bytes().as_ref(),
+            pool_manager.num_timelocked_actions.to_le_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + TimelockedAction::LEN,
+        payer = payer
+    )]
+    pub timelocked_action: Account<'info, TimelockedAction>,
+    pub admin: Signer<'info>,
+    /// Payer of the [TimelockedAction] initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for [pools::execute_timelocked_manager_action].
+#[derive(Accounts)]
+pub struct ExecuteTimelockedManagerAction<'info> {
+    #[account(mut, constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(mut)]
+    pub timelocked_action: Account<'info, TimelockedAction>,
+}
+
+/// Accounts for [pools::execute_timelocked_swap_action].
+#[derive(Accounts)]
+pub struct ExecuteTimelockedSwapAction<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(mut)]
+    pub timelocked_action: Account<'info, TimelockedAction>,
+    #[account(mut)]
+    pub swap: Account<'info, SwapInfo>,
+    #[account(
+        mut,
+        has_one = swap,
+        constraint = pool.manager == pool_manager.key(),
+        constraint = pool.is_migrated() @ ErrorCode::AccountNeedsMigration
+    )]
+    pub pool: Account<'info, Pool>,
+    pub swap_program: Program<'info, StableSwap>,
+}
+
+/// Accounts for [pools::cancel_timelocked_action].
+#[derive(Accounts)]
+pub struct CancelTimelockedAction<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(mut)]
+    pub timelocked_action: Account<'info, TimelockedAction>,
+    /// The admin or guardian of the [PoolManager].
+    pub admin_or_guardian: Signer<'info>,
+}
+
+/// Accounts for [pools::enable_approvals].
+#[derive(Accounts)]
+pub struct EnableApprovals<'info> {
+    #[account(mut, constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    /// The [ApprovalConfig].
+    #[account(
+        init,
+        seeds = [
+            b"SaberApprovalConfig".as_ref(),
+            pool_manager.key().to_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + ApprovalConfig::LEN,
+        payer = payer
+    )]
+    pub approval_config: Account<'info, ApprovalConfig>,
+    pub admin: Signer<'info>,
+    /// Payer of the [ApprovalConfig] initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for [pools::set_approvers].
+#[derive(Accounts)]
+pub struct SetApprovers<'info> {
+    #[account(constraint = pool_manager.is_migrated() @ ErrorCode::AccountNeedsMigration)]
+    pub pool_manager: Account<'info, PoolManager>,
+    #[account(mut)]
+    pub approval_config: Account<'info, ApprovalConfig>,
+    pub admin: Signer<'info>,
+}
+
+/// Accounts for [pools::create_proposal].
+#[derive(Accounts)]
+#[instruction(instruction: ProposalInstruction)]
+pub struct CreateProposal<'info> {
+    #[account(mut)]
+    pub approval_config: Account<'info, ApprovalConfig>,
+    /// The [Proposal].
+    #[account(
+        init,
+        seeds = [
+            b"SaberProposal".as_ref(),
+            approval_config.manager.to_bytes().as_ref(),
+            approval_config.num_proposals.to_le_bytes().as_ref()
+        ],
+        bump,
+        space = 8 + Proposal::space(&instruction),
+        payer = payer
+    )]
+    pub proposal: Account<'info, Proposal>,
+    /// An approver of the [ApprovalConfig].
+    pub proposer: Signer<'info>,
+    /// Payer of the [Proposal] initialization.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for [pools::approve_proposal].
+#[derive(Accounts)]
+pub struct ApproveProposal<'info> {
+    pub approval_config: Account<'info, ApprovalConfig>,
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+    /// An approver of the [ApprovalConfig].
+    pub approver: Signer<'info>,
+}
+
+/// Accounts for [pools::execute_proposal].
+#[derive(Accounts)]
+pub struct ExecuteProposal<'info> {
+    pub approval_config: Account<'info, ApprovalConfig>,
+    #[account(mut)]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Accounts for [pools::migrate_pool_manager].
+#[derive(Accounts)]
+pub struct MigratePoolManager<'info> {
+    /// The [PoolManager] to migrate.
+    /// CHECK: Checked in [pools::migrate_pool_manager].
+    #[account(mut)]
+    pub pool_manager: UncheckedAccount<'info>,
+    /// Payer of the additional rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
+/// Accounts for [pools::migrate_pool].
+#[derive(Accounts)]
+pub struct MigratePool<'info> {
+    /// The [Pool] to migrate.
+    /// CHECK: Checked in [pools::migrate_pool].
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+    /// Payer of the additional rent.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// [System] program.
+    pub system_program: Program<'info, System>,
+}
+
 /// Error codes.
 #[error_code]
 pub enum ErrorCode {
     #[msg("Must be admin to perform this action.")]
     NotAdmin,
-    #[msg("Must be admin or operator to perform this action.")]
+    #[msg("Must be admin, operator or importer to perform this action.")]
     NotAdminOrOperator,
     #[msg("Initial amp factor out of range.")]
     InitialAmpOutOfRange,
@@ -402,4 +2495,154 @@ pub enum ErrorCode {
     SwapTokensCannotBeEqual,
     #[msg("Specified fee account invalid.")]
     InvalidFeeAccount,
+    #[msg("Fees must be scheduled at least the minimum notice period in advance.")]
+    FeeNoticePeriodTooShort,
+    #[msg("Notice period cannot be negative.")]
+    InvalidNoticePeriod,
+    #[msg("No fees are scheduled on this pool.")]
+    NoScheduledFees,
+    #[msg("Scheduled fees are not yet effective.")]
+    ScheduledFeesNotEffective,
+    #[msg("Fees exceed the caps configured on the pool manager.")]
+    FeesExceedCaps,
+    #[msg("Fee caps cannot exceed 100%.")]
+    InvalidFeeCaps,
+    #[msg("No fee caps are pending.")]
+    NoPendingFeeCaps,
+    #[msg("Pending fee caps are not yet effective.")]
+    PendingFeeCapsNotEffective,
+    #[msg("Batch accounts must be a non-empty list of (pool, swap) pairs.")]
+    InvalidBatchAccounts,
+    #[msg("This action must be performed through the timelock.")]
+    TimelockRequired,
+    #[msg("Timelock delay cannot be negative.")]
+    InvalidTimelockDelay,
+    #[msg("Timelocked action cannot be executed before its ETA.")]
+    TimelockedActionNotReady,
+    #[msg("Timelocked action has already been executed.")]
+    TimelockedActionAlreadyExecuted,
+    #[msg("Timelocked action has been canceled.")]
+    TimelockedActionCanceled,
+    #[msg("Timelocked action cannot be executed by this instruction.")]
+    TimelockedActionKindMismatch,
+    #[msg("The new admin account must be passed to commit a new admin.")]
+    MissingNewAdmin,
+    #[msg(
+        "Approvers must be unique and the threshold must be between 1 and the number of approvers."
+    )]
+    InvalidApprovers,
+    #[msg("Must be an approver to perform this action.")]
+    NotApprover,
+    #[msg("Proposal must contain instruction data.")]
+    EmptyProposal,
+    #[msg("Proposal was created with a different set of approvers.")]
+    ProposalApproversChanged,
+    #[msg("Proposal does not have enough approvals.")]
+    NotEnoughApprovals,
+    #[msg("Proposal has already been executed.")]
+    ProposalAlreadyExecuted,
+    #[msg("Must be admin or hold the required role to perform this action.")]
+    MissingRole,
+    #[msg("Unknown role.")]
+    InvalidRoles,
+    #[msg("Must be the pending operator to perform this action.")]
+    NotPendingOperator,
+    #[msg("Must be the pending beneficiary to perform this action.")]
+    NotPendingBeneficiary,
+    #[msg("This parameter has been locked and can no longer be changed.")]
+    ParamLocked,
+    #[msg("Unknown parameter.")]
+    InvalidParams,
+    #[msg("Amp factor bounds must be non-zero and the minimum cannot exceed the maximum.")]
+    InvalidAmpBounds,
+    #[msg("Must be the creator of the pool to perform this action.")]
+    NotPoolCreator,
+    #[msg("Target amp factor is outside of the permissionless amp factor bounds.")]
+    AmpOutOfRange,
+    #[msg("Ramp policy must allow a change factor of at least 1 and non-negative durations.")]
+    InvalidRampPolicy,
+    #[msg("Ramp changes the amp factor by more than the ramp policy allows.")]
+    RampChangeTooLarge,
+    #[msg("Ramp is shorter than the ramp policy allows.")]
+    RampTooShort,
+    #[msg("Pool was ramped too recently.")]
+    RampCooldownNotElapsed,
+    #[msg("Amp schedule steps must have non-zero targets and increasing stop times.")]
+    InvalidAmpSchedule,
+    #[msg("Amp schedule has no steps left.")]
+    AmpScheduleComplete,
+    #[msg("The current ramp has not yet ended.")]
+    RampInProgress,
+    #[msg(
+        "Swap cannot be ramped until the minimum ramp duration has passed since the last ramp started."
+    )]
+    RampLocked,
+    #[msg("Ramp must last at least the stable-swap minimum ramp duration.")]
+    InsufficientRampTime,
+    #[msg("Target amp factor is outside of the stable-swap amp factor bounds.")]
+    TargetAmpOutOfRange,
+    #[msg("Ramp changes the amp factor by more than stable-swap allows.")]
+    AmpChangeExceedsSwapLimit,
+    #[msg("Imbalance threshold cannot exceed 100%.")]
+    InvalidImbalanceThreshold,
+    #[msg("The circuit breaker is disabled on this pool manager.")]
+    CircuitBreakerDisabled,
+    #[msg("Swap reserves are not imbalanced enough to trip the circuit breaker.")]
+    ImbalanceWithinThreshold,
+    #[msg("Peg price must be positive and the deviation band must be between 0 and 100%.")]
+    InvalidOracleConfig,
+    #[msg("Price account is not a trading Pyth-style price account.")]
+    InvalidPriceAccount,
+    #[msg("Oracle price is too old.")]
+    StaleOraclePrice,
+    #[msg("Oracle price is within the configured band.")]
+    PriceWithinBand,
+    #[msg("Pool does not hold the mint.")]
+    PoolMintMismatch,
+    #[msg("Importing pools is disabled on this pool manager.")]
+    ImportsDisabled,
+    #[msg("Unknown import check.")]
+    InvalidImportChecks,
+    #[msg("Swap failed the import checks required by the pool manager.")]
+    ImportChecksFailed,
+    #[msg("Swap admin must have committed the pool as its new admin.")]
+    SwapAdminNotCommitted,
+    #[msg("Cannot recover tokens from the fee accounts or swap reserves.")]
+    CannotRecoverPoolTokens,
+    #[msg("Account to migrate must be owned by the program and of the expected type.")]
+    InvalidMigrationAccount,
+    #[msg("Account is already at the current version.")]
+    AccountAlreadyMigrated,
+    #[msg("Fees must be scheduled while a minimum fee notice period is set.")]
+    FeeNoticeRequired,
+    #[msg("No notice period change is pending.")]
+    NoPendingNoticePeriod,
+    #[msg("Pending notice period is not yet effective.")]
+    PendingNoticePeriodNotEffective,
+    #[msg("No swap admin change is pending.")]
+    NoPendingSwapAdmin,
+    #[msg("Pending swap admin is not yet effective.")]
+    PendingSwapAdminNotEffective,
+    #[msg("Timelocked action has expired.")]
+    TimelockedActionExpired,
+    #[msg("No ramp policy is pending.")]
+    NoPendingRampPolicy,
+    #[msg("Pending ramp policy is not yet effective.")]
+    PendingRampPolicyNotEffective,
+    #[msg("Swap is already paused.")]
+    SwapAlreadyPaused,
+    #[msg("Must be the admin of the swap.")]
+    NotSwapAdmin,
+    #[msg("Account must be migrated to the current version first.")]
+    AccountNeedsMigration,
+    #[msg("Must be admin, guardian or pauser to perform this action.")]
+    NotAdminOrGuardian,
+    #[msg("The imbalance of the swap reserves could not be computed.")]
+    ImbalanceNotComputable,
+    #[msg("The admin has been renounced.")]
+    AdminRenounced,
+    #[msg("The swap admin change delay may not be lowered once pools exist.")]
+    SwapAdminChangeDelayTooShort,
+    #[msg("Swap admin change delay must not be negative.")]
+    InvalidSwapAdminChangeDelay,
 }
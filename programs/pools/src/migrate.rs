@@ -0,0 +1,42 @@
+//! Migrates accounts created with an older layout.
+
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use vipers::prelude::*;
+
+/// Resizes a program account with the given discriminator to `len` bytes,
+/// with the payer funding any additional rent. New bytes are zeroed.
+pub fn realloc_account<'info>(
+    account: &AccountInfo<'info>,
+    discriminator: [u8; 8],
+    len: usize,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+) -> Result<()> {
+    invariant!(*account.owner == crate::ID, InvalidMigrationAccount);
+    invariant!(
+        account.data_len() >= 8 && account.try_borrow_data()?[..8] == discriminator,
+        InvalidMigrationAccount
+    );
+    if account.data_len() >= len {
+        return Ok(());
+    }
+
+    let rent_exempt_minimum = Rent::get()?.minimum_balance(len);
+    let missing_lamports = rent_exempt_minimum.saturating_sub(account.lamports());
+    if missing_lamports > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            missing_lamports,
+        )?;
+    }
+
+    account.realloc(len, true)?;
+    Ok(())
+}
@@ -0,0 +1,71 @@
+//! Parsing of Pyth-style price accounts.
+
+/// Magic number at the start of a price account.
+const MAGIC: u32 = 0xa1b2_c3d4;
+/// Account type of a price account.
+const PRICE_ACCOUNT_TYPE: u32 = 3;
+/// Status of a price which is currently trading.
+const STATUS_TRADING: u32 = 1;
+
+const MAGIC_OFFSET: usize = 0;
+const ACCOUNT_TYPE_OFFSET: usize = 8;
+const EXPO_OFFSET: usize = 20;
+const AGG_PRICE_OFFSET: usize = 208;
+const AGG_CONF_OFFSET: usize = 216;
+const AGG_STATUS_OFFSET: usize = 224;
+const AGG_PUB_SLOT_OFFSET: usize = 232;
+
+/// The aggregate price of a price account.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OraclePrice {
+    /// Price, in units of `10^expo`.
+    pub price: i64,
+    /// Confidence interval, in units of `10^expo`.
+    pub conf: u64,
+    /// Price exponent.
+    pub expo: i32,
+    /// Slot at which the price was published.
+    pub pub_slot: u64,
+}
+
+/// Parses the aggregate price of a Pyth-style price account.
+/// Returns [None] if the data is not a price account or the price is not trading.
+pub fn parse_price(data: &[u8]) -> Option<OraclePrice> {
+    if read_u32(data, MAGIC_OFFSET)? != MAGIC
+        || read_u32(data, ACCOUNT_TYPE_OFFSET)? != PRICE_ACCOUNT_TYPE
+        || read_u32(data, AGG_STATUS_OFFSET)? != STATUS_TRADING
+    {
+        return None;
+    }
+    Some(OraclePrice {
+        price: i64::from_le_bytes(read_bytes(data, AGG_PRICE_OFFSET)?),
+        conf: u64::from_le_bytes(read_bytes(data, AGG_CONF_OFFSET)?),
+        expo: i32::from_le_bytes(read_bytes(data, EXPO_OFFSET)?),
+        pub_slot: u64::from_le_bytes(read_bytes(data, AGG_PUB_SLOT_OFFSET)?),
+    })
+}
+
+/// Computes the deviation of `price * 10^expo` from `peg_price * 10^peg_expo`, in bps.
+pub fn deviation_bps(price: i64, expo: i32, peg_price: i64, peg_expo: i32) -> Option<u64> {
+    // rescale both prices to the smaller exponent
+    let min_expo = expo.min(peg_expo);
+    let price = rescale(price, expo.checked_sub(min_expo)?)?;
+    let peg_price = rescale(peg_price, peg_expo.checked_sub(min_expo)?)?;
+    if peg_price <= 0 {
+        return None;
+    }
+    let deviation = price.checked_sub(peg_price)?.checked_abs()?;
+    u64::try_from(deviation.checked_mul(10_000)?.checked_div(peg_price)?).ok()
+}
+
+fn rescale(price: i64, shift: i32) -> Option<i128> {
+    i128::from(price).checked_mul(10_i128.checked_pow(u32::try_from(shift).ok()?)?)
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(read_bytes(data, offset)?))
+}
+
+fn read_bytes<const N: usize>(data: &[u8], offset: usize) -> Option<[u8; N]> {
+    data.get(offset..offset.checked_add(N)?)?.try_into().ok()
+}
@@ -1,6 +1,9 @@
 //! Accounts state.
 
-use anchor_lang::{prelude::*, solana_program::pubkey::PUBKEY_BYTES};
+use anchor_lang::{
+    prelude::*,
+    solana_program::{instruction::AccountMeta, pubkey::PUBKEY_BYTES},
+};
 use stable_swap_anchor::SwapInfo;
 
 /// Manages all [Pool]s.
@@ -32,12 +35,174 @@ pub struct PoolManager {
 
     /// Account which all fees may be withdrawn to.
     pub beneficiary: Pubkey,
+
+    /// Minimum number of seconds between scheduling new fees and them taking effect.
+    pub min_fee_notice_period: i64,
+
+    /// Maximum fees which may be set on managed swaps.
+    pub fee_caps: FeeCaps,
+    /// Fee caps which will replace [PoolManager::fee_caps] once applied.
+    pub pending_fee_caps: FeeCaps,
+    /// Time after which [PoolManager::pending_fee_caps] may be applied.
+    /// Zero if no fee caps are pending.
+    pub pending_fee_caps_effective_ts: i64,
+
+    /// Account which may pause, but never unpause, swaps.
+    pub guardian: Pubkey,
+
+    /// Number of seconds a [TimelockedAction] must wait before being executed.
+    /// If non-zero, privileged actions may only be performed through the timelock.
+    pub timelock_delay: i64,
+    /// Total number of [TimelockedAction]s queued.
+    pub num_timelocked_actions: u64,
+
+    /// Time after which the [PoolManager::operator] may no longer act.
+    pub operator_valid_until: i64,
+
+    /// The next operator of the [PoolManager], once accepted.
+    pub pending_operator: Pubkey,
+    /// [PoolManager::operator_valid_until] of the pending operator.
+    pub pending_operator_valid_until: i64,
+    /// The next beneficiary of the [PoolManager], once accepted.
+    pub pending_beneficiary: Pubkey,
+
+    /// Bitmask of [params] which may no longer be changed.
+    /// Bits may be set but never cleared.
+    pub locked_params: u16,
+
+    /// Limits on ramping the amplification coefficient of managed swaps.
+    pub ramp_policy: RampPolicy,
+
+    /// Deviation of the marginal price of a swap from 1, in bps, above which anyone may pause
+    /// the swap via [crate::pools::trip_circuit_breaker]. Zero disables the circuit breaker.
+    pub max_imbalance_bps: u16,
+
+    /// Total number of [MintIncident]s recorded.
+    pub num_mint_incidents: u64,
+
+    /// Flag indicating if [crate::pools::import_pool_permissionless] may be called.
+    pub permissionless_imports_enabled: bool,
+    /// Flag indicating if [crate::pools::import_pool_as_operator] may be called.
+    pub operator_imports_enabled: bool,
+
+    /// Bitmask of [import_checks] required of pools imported via
+    /// [crate::pools::import_pool_permissionless].
+    pub import_checks: u16,
+
+    /// Notice period which will replace [PoolManager::min_fee_notice_period] once applied.
+    pub pending_min_fee_notice_period: i64,
+    /// Time after which [PoolManager::pending_min_fee_notice_period] may be applied.
+    /// Zero if no notice period change is pending.
+    pub pending_min_fee_notice_period_effective_ts: i64,
+
+    /// Flag indicating [crate::pools::enable_approvals] has been called.
+    /// Only [Proposal]s may then act as the admin; the [PoolManager::admin] key alone may not.
+    pub approvals_required: bool,
+
+    /// Ramp policy which will replace [PoolManager::ramp_policy] once applied.
+    pub pending_ramp_policy: RampPolicy,
+    /// Time after which [PoolManager::pending_ramp_policy] may be applied.
+    /// Zero if no ramp policy is pending.
+    pub pending_ramp_policy_effective_ts: i64,
+
+    /// Number of seconds between committing a new admin of a swap and being able to apply it.
+    pub swap_admin_change_delay: i64,
+
+    /// Layout version of the account. Zero if created before versioning was introduced.
+    pub version: u8,
+    /// Reserved for future fields, so they may be added without resizing the account.
+    pub reserved: [u64; 16],
 }
 
 impl PoolManager {
     /// Number of bytes in a serialized [PoolManager].
-    pub const LEN: usize =
-        PUBKEY_BYTES + 1 + 8 + PUBKEY_BYTES * 2 + SwapFees::LEN + 8 + 8 + PUBKEY_BYTES * 2;
+    pub const LEN: usize = PUBKEY_BYTES
+        + 1
+        + 8
+        + PUBKEY_BYTES * 2
+        + SwapFees::LEN
+        + 8
+        + 8
+        + PUBKEY_BYTES * 2
+        + 8
+        + FeeCaps::LEN * 2
+        + 8
+        + PUBKEY_BYTES
+        + 8
+        + 8
+        + 8
+        + PUBKEY_BYTES
+        + 8
+        + PUBKEY_BYTES
+        + 2
+        + RampPolicy::LEN
+        + 2
+        + 8
+        + 1
+        + 1
+        + 2
+        + 8
+        + 8
+        + 1
+        + RampPolicy::LEN
+        + 8
+        + 8
+        + 1
+        + 8 * 16;
+
+    /// Current layout version of [PoolManager] accounts.
+    pub const VERSION: u8 = 1;
+
+    /// Default [PoolManager::min_fee_notice_period].
+    pub const DEFAULT_MIN_FEE_NOTICE_PERIOD: i64 = 86_400;
+
+    /// Fills the fields added since [PoolManager::version] with their defaults
+    /// and sets the current version.
+    pub fn migrate(&mut self) {
+        if self.version < 1 {
+            self.operator_valid_until = i64::MAX;
+            self.min_fee_notice_period = Self::DEFAULT_MIN_FEE_NOTICE_PERIOD;
+            self.fee_caps = FeeCaps::DEFAULT;
+            self.ramp_policy = RampPolicy::DEFAULT;
+            self.permissionless_imports_enabled = true;
+            self.operator_imports_enabled = true;
+            self.swap_admin_change_delay = crate::SWAP_ADMIN_CHANGE_DELAY;
+        }
+        self.version = Self::VERSION;
+    }
+
+    /// Returns true if the account has been migrated to [PoolManager::VERSION].
+    /// Instructions reject accounts which have not.
+    pub fn is_migrated(&self) -> bool {
+        self.version == Self::VERSION
+    }
+
+    /// Returns true if privileged actions must go through the timelock.
+    pub fn is_timelocked(&self) -> bool {
+        self.timelock_delay > 0
+    }
+
+    /// Returns true if the account is the [PoolManager::operator] and has not expired.
+    /// The operator is inactive once the admin has been renounced.
+    pub fn is_active_operator(&self, key: &Pubkey, now: i64) -> bool {
+        !self.is_admin_renounced() && *key == self.operator && now <= self.operator_valid_until
+    }
+
+    /// Returns true if the account is the [PoolManager::guardian].
+    /// The guardian is inactive once the admin has been renounced.
+    pub fn is_guardian(&self, key: &Pubkey) -> bool {
+        !self.is_admin_renounced() && *key == self.guardian
+    }
+
+    /// Returns true if any of the given [params] are locked.
+    pub fn is_locked(&self, params: u16) -> bool {
+        self.locked_params & params != 0
+    }
+
+    /// Returns true if the admin has been renounced via [crate::pools::renounce_admin].
+    pub fn is_admin_renounced(&self) -> bool {
+        self.admin == Pubkey::default()
+    }
 }
 
 /// The admin of a [stable_swap_anchor::SwapInfo].
@@ -69,11 +234,646 @@ pub struct Pool {
     pub token_decimals: u8,
     /// Flag indicating if the pool was imported with [crate::pools::import_pool_permissionless].
     pub permissionless_import: bool,
+
+    /// Fees scheduled via [crate::pools::schedule_new_fees].
+    pub scheduled_fees: SwapFees,
+    /// Time after which [Pool::scheduled_fees] may be applied.
+    /// Zero if no fees are scheduled.
+    pub scheduled_fees_effective_ts: i64,
+
+    /// Account which may ramp, pause and set fees on this pool only.
+    /// The default [Pubkey] if there is no delegate.
+    pub pool_admin: Pubkey,
+
+    /// Account which imported the pool via [crate::pools::import_pool_permissionless].
+    /// It may ramp the amplification coefficient within the permissionless bounds.
+    /// The default [Pubkey] if the pool was not imported permissionlessly.
+    pub creator: Pubkey,
+
+    /// Time of the last ramp of the amplification coefficient.
+    pub last_ramp_ts: i64,
+
+    /// Flag indicating the swap was paused by [crate::pools::trip_circuit_breaker]
+    /// or [crate::pools::check_depeg].
+    /// Only the admin may unpause it.
+    pub circuit_breaker_tripped: bool,
+
+    /// Flag indicating the mints of the swap are not sorted,
+    /// i.e. [Pool::mint_b] and [Pool::token_b_fees] are of the sorted token A.
+    /// [Pool::mint_a] and [Pool::mint_b] always follow the order of the swap.
+    pub swap_tokens_reversed: bool,
+
+    /// Admin of the swap committed via [crate::pools::commit_new_admin].
+    pub pending_swap_admin: Pubkey,
+    /// Time after which [Pool::pending_swap_admin] may be applied.
+    /// Zero if no swap admin change is pending.
+    pub pending_swap_admin_effective_ts: i64,
+
+    /// Layout version of the account. Zero if created before versioning was introduced.
+    pub version: u8,
+    /// Reserved for future fields, so they may be added without resizing the account.
+    pub reserved: [u64; 8],
 }
 
 impl Pool {
     /// Number of bytes in a serialized [Pool].
-    pub const LEN: usize = PUBKEY_BYTES * 3 + 1 + PUBKEY_BYTES + 8 + PUBKEY_BYTES * 3 + 1 + 1;
+    pub const LEN: usize = PUBKEY_BYTES * 3
+        + 1
+        + PUBKEY_BYTES
+        + 8
+        + PUBKEY_BYTES * 3
+        + 1
+        + 1
+        + SwapFees::LEN
+        + 8
+        + PUBKEY_BYTES * 2
+        + 8
+        + 1
+        + 1
+        + PUBKEY_BYTES
+        + 8
+        + 1
+        + 8 * 8;
+
+    /// Current layout version of [Pool] accounts.
+    pub const VERSION: u8 = 1;
+
+    /// Fills the fields added since [Pool::version] with their defaults
+    /// and sets the current version.
+    pub fn migrate(&mut self) {
+        if self.version < 1 {
+            self.swap_tokens_reversed = self.mint_a > self.mint_b;
+        }
+        self.version = Self::VERSION;
+    }
+
+    /// Returns true if the account has been migrated to [Pool::VERSION].
+    /// Instructions reject accounts which have not.
+    pub fn is_migrated(&self) -> bool {
+        self.version == Self::VERSION
+    }
+
+    /// Returns true if fees are scheduled to be applied to the swap.
+    pub fn has_scheduled_fees(&self) -> bool {
+        self.scheduled_fees_effective_ts != 0
+    }
+
+    /// Returns true if the account is the [Pool::creator].
+    pub fn is_creator(&self, key: &Pubkey) -> bool {
+        self.creator != Pubkey::default() && *key == self.creator
+    }
+
+    /// Returns true if the account is the [Pool::pool_admin].
+    pub fn is_pool_admin(&self, key: &Pubkey) -> bool {
+        self.pool_admin != Pubkey::default() && *key == self.pool_admin
+    }
+}
+
+/// Limits on ramping the amplification coefficient of the swaps of a [PoolManager].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct RampPolicy {
+    /// Maximum factor by which a single ramp may multiply or divide the amplification coefficient.
+    pub max_change_factor: u64,
+    /// Minimum number of seconds a ramp must take.
+    pub min_duration: i64,
+    /// Minimum number of seconds between the start of two ramps of the same [Pool].
+    pub min_cooldown: i64,
+}
+
+impl RampPolicy {
+    /// Number of bytes in a serialized [RampPolicy].
+    pub const LEN: usize = 8 * 3;
+
+    /// Policy set on new [PoolManager]s.
+    pub const DEFAULT: RampPolicy = RampPolicy {
+        max_change_factor: 10,
+        min_duration: 86_400,
+        min_cooldown: 86_400,
+    };
+
+    /// Returns true if the policy is well-formed.
+    pub fn is_valid(&self) -> bool {
+        self.max_change_factor >= 1 && self.min_duration >= 0 && self.min_cooldown >= 0
+    }
+
+    /// Returns true if ramping from `current_amp` to `target_amp` is within
+    /// [RampPolicy::max_change_factor].
+    pub fn allows_change(&self, current_amp: u64, target_amp: u64) -> bool {
+        let max_change_factor = u128::from(self.max_change_factor);
+        u128::from(target_amp) <= u128::from(current_amp) * max_change_factor
+            && u128::from(current_amp) <= u128::from(target_amp) * max_change_factor
+    }
+}
+
+/// Price oracle configuration of a [anchor_spl::token::Mint] of a [PoolManager],
+/// used to pause swaps holding the mint via [crate::pools::check_depeg].
+#[account]
+#[derive(Copy, Default, Debug)]
+pub struct MintOracle {
+    /// The [PoolManager].
+    pub manager: Pubkey,
+    /// The [anchor_spl::token::Mint].
+    pub mint: Pubkey,
+    /// Bump seed
+    pub bump: u8,
+
+    /// Pyth-style price account of the mint.
+    pub price_account: Pubkey,
+    /// Price the mint is pegged to, in units of `10^peg_expo`.
+    pub peg_price: i64,
+    /// Exponent of [MintOracle::peg_price].
+    pub peg_expo: i32,
+    /// Deviation from the peg, in bps, above which swaps holding the mint may be paused.
+    pub max_deviation_bps: u16,
+    /// Maximum age of the price, in slots, for it to be used.
+    pub max_staleness_slots: u64,
+}
+
+impl MintOracle {
+    /// Number of bytes in a serialized [MintOracle].
+    pub const LEN: usize = PUBKEY_BYTES * 2 + 1 + PUBKEY_BYTES + 8 + 4 + 2 + 8;
+}
+
+/// Record of the swaps holding a [anchor_spl::token::Mint] being paused
+/// via [crate::pools::pause_by_mint].
+#[account]
+#[derive(Copy, Default, Debug)]
+pub struct MintIncident {
+    /// The [PoolManager].
+    pub manager: Pubkey,
+    /// Incident index, 0-indexed.
+    pub index: u64,
+    /// Bump seed
+    pub bump: u8,
+
+    /// The [anchor_spl::token::Mint].
+    pub mint: Pubkey,
+    /// Account which paused the swaps.
+    pub authority: Pubkey,
+    /// Number of swaps paused.
+    pub num_swaps_paused: u64,
+    /// Time the swaps were paused.
+    pub created_at: i64,
+}
+
+impl MintIncident {
+    /// Number of bytes in a serialized [MintIncident].
+    pub const LEN: usize = PUBKEY_BYTES + 8 + 1 + PUBKEY_BYTES * 2 + 8 + 8;
+}
+
+/// Maximum number of steps in an [AmpSchedule].
+pub const MAX_AMP_SCHEDULE_STEPS: usize = 8;
+
+/// A sequence of amplification coefficient ramps of a [Pool],
+/// started one after another via [crate::pools::advance_amp_schedule].
+#[account]
+#[derive(Default, Debug)]
+pub struct AmpSchedule {
+    /// The [Pool].
+    pub pool: Pubkey,
+    /// Bump seed
+    pub bump: u8,
+
+    /// Index of the next step to start.
+    pub next_step: u8,
+    /// Ramps to perform, in order.
+    pub steps: Vec<AmpScheduleStep>,
+}
+
+impl AmpSchedule {
+    /// Number of bytes in a serialized [AmpSchedule] with [MAX_AMP_SCHEDULE_STEPS] steps.
+    pub const LEN: usize = PUBKEY_BYTES + 1 + 1 + 4 + AmpScheduleStep::LEN * MAX_AMP_SCHEDULE_STEPS;
+
+    /// The next step to start, if any.
+    pub fn next(&self) -> Option<AmpScheduleStep> {
+        self.steps.get(usize::from(self.next_step)).copied()
+    }
+}
+
+/// A ramp of an [AmpSchedule].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct AmpScheduleStep {
+    /// Amplification coefficient to ramp to.
+    pub target_amp: u64,
+    /// Time at which the ramp ends.
+    pub stop_ramp_ts: i64,
+}
+
+impl AmpScheduleStep {
+    /// Number of bytes in a serialized [AmpScheduleStep].
+    pub const LEN: usize = 8 + 8;
+}
+
+/// A privileged [PoolManager] action which may only be executed after a delay.
+#[account]
+#[derive(Copy, Debug)]
+pub struct TimelockedAction {
+    /// The [PoolManager].
+    pub manager: Pubkey,
+    /// Queue index, 0-indexed.
+    pub index: u64,
+    /// Bump seed
+    pub bump: u8,
+
+    /// Account which queued the action.
+    pub proposer: Pubkey,
+    /// The action to perform.
+    pub action: TimelockedActionKind,
+
+    /// Time after which the action may be executed.
+    /// The action expires [crate::TIMELOCK_GRACE_PERIOD] after this time.
+    pub eta: i64,
+    /// Time the action was executed. Zero if not executed.
+    pub executed_at: i64,
+    /// Time the action was canceled. Zero if not canceled.
+    pub canceled_at: i64,
+}
+
+impl TimelockedAction {
+    /// Number of bytes in a serialized [TimelockedAction].
+    pub const LEN: usize = PUBKEY_BYTES + 8 + 1 + PUBKEY_BYTES + TimelockedActionKind::LEN + 8 * 3;
+}
+
+/// An action which may be queued in a [TimelockedAction].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TimelockedActionKind {
+    /// Sets new fees on the swap of a [Pool].
+    SetNewFees { pool: Pubkey, new_fees: SwapFees },
+    /// Ramps the amplification coefficient of the swap of a [Pool].
+    RampA {
+        pool: Pubkey,
+        target_amp: u64,
+        stop_ramp_ts: i64,
+    },
+    /// Commits a new admin to the swap of a [Pool].
+    /// The time spent in the timelock counts towards [PoolManager::swap_admin_change_delay].
+    CommitNewAdmin { pool: Pubkey, new_admin: Pubkey },
+    /// Commits a new [PoolManager::operator], who must then accept.
+    SetOperator { operator: Pubkey, valid_until: i64 },
+    /// Commits a new [PoolManager::beneficiary], who must then accept.
+    SetBeneficiary { beneficiary: Pubkey },
+    /// Sets the [PoolManager::timelock_delay].
+    SetTimelockDelay { timelock_delay: i64 },
+}
+
+impl TimelockedActionKind {
+    /// Maximum number of bytes in a serialized [TimelockedActionKind].
+    pub const LEN: usize = 1 + PUBKEY_BYTES + SwapFees::LEN;
+
+    /// The [Pool] this action operates on, if any.
+    pub fn pool(&self) -> Option<Pubkey> {
+        match self {
+            TimelockedActionKind::SetNewFees { pool, .. }
+            | TimelockedActionKind::RampA { pool, .. }
+            | TimelockedActionKind::CommitNewAdmin { pool, .. } => Some(*pool),
+            TimelockedActionKind::SetOperator { .. }
+            | TimelockedActionKind::SetBeneficiary { .. }
+            | TimelockedActionKind::SetTimelockDelay { .. } => None,
+        }
+    }
+
+    /// The [params] this action modifies, if any.
+    pub fn params(&self) -> u16 {
+        match self {
+            TimelockedActionKind::SetNewFees { .. } => params::POOL_FEES,
+            TimelockedActionKind::RampA { .. } => params::POOL_RAMPS,
+            TimelockedActionKind::SetOperator { .. } => params::OPERATOR,
+            TimelockedActionKind::SetBeneficiary { .. } => params::BENEFICIARY,
+            TimelockedActionKind::CommitNewAdmin { .. } => params::SWAP_ADMIN,
+            TimelockedActionKind::SetTimelockDelay { .. } => 0,
+        }
+    }
+}
+
+/// Parameters of a [PoolManager] which may be locked via [crate::pools::lock_params].
+/// Once locked, a parameter can never be changed again.
+pub mod params {
+    /// [super::PoolManager::initial_fees].
+    pub const INITIAL_FEES: u16 = 1 << 0;
+    /// [super::PoolManager::min_permissionless_amp_factor] and
+    /// [super::PoolManager::max_permissionless_amp_factor].
+    pub const AMP_BOUNDS: u16 = 1 << 1;
+    /// [super::PoolManager::beneficiary].
+    pub const BENEFICIARY: u16 = 1 << 2;
+    /// [super::PoolManager::operator].
+    pub const OPERATOR: u16 = 1 << 3;
+    /// Fees of managed swaps.
+    pub const POOL_FEES: u16 = 1 << 4;
+    /// Amplification coefficient ramps of managed swaps and [super::PoolManager::ramp_policy].
+    pub const POOL_RAMPS: u16 = 1 << 5;
+    /// Admins of managed swaps.
+    pub const SWAP_ADMIN: u16 = 1 << 6;
+
+    /// All parameters.
+    pub const ALL: u16 =
+        INITIAL_FEES | AMP_BOUNDS | BENEFICIARY | OPERATOR | POOL_FEES | POOL_RAMPS | SWAP_ADMIN;
+
+    /// Returns true if the bitmask only contains known parameters.
+    pub fn are_valid(params: u16) -> bool {
+        params & !ALL == 0
+    }
+}
+
+/// Optional checks on swaps imported via [crate::pools::import_pool_permissionless],
+/// enabled through [PoolManager::import_checks].
+pub mod import_checks {
+    /// Neither token mint has a freeze authority.
+    pub const NO_MINT_FREEZE_AUTHORITY: u16 = 1 << 0;
+    /// The mint authority of the LP mint is the swap authority.
+    pub const LP_MINT_AUTHORITY_IS_SWAP: u16 = 1 << 1;
+    /// The LP mint has no freeze authority.
+    pub const NO_LP_FREEZE_AUTHORITY: u16 = 1 << 2;
+    /// The swap is not paused.
+    pub const NOT_PAUSED: u16 = 1 << 3;
+    /// The swap is not ramping its amplification coefficient.
+    pub const NO_RAMP_IN_PROGRESS: u16 = 1 << 4;
+    /// Both reserves of the swap hold tokens.
+    pub const NONZERO_RESERVES: u16 = 1 << 5;
+    /// The swap has no pending admin.
+    pub const NO_PENDING_ADMIN: u16 = 1 << 6;
+
+    /// All checks.
+    pub const ALL: u16 = NO_MINT_FREEZE_AUTHORITY
+        | LP_MINT_AUTHORITY_IS_SWAP
+        | NO_LP_FREEZE_AUTHORITY
+        | NOT_PAUSED
+        | NO_RAMP_IN_PROGRESS
+        | NONZERO_RESERVES
+        | NO_PENDING_ADMIN;
+
+    /// Returns true if the bitmask only contains known checks.
+    pub fn are_valid(checks: u16) -> bool {
+        checks & !ALL == 0
+    }
+}
+
+/// Failures reported by [crate::pools::check_import].
+pub mod import_failures {
+    /// [super::PoolManager::permissionless_imports_enabled] is false.
+    pub const IMPORTS_DISABLED: u32 = 1 << 0;
+    /// The admin of the swap is not the [super::Pool].
+    pub const SWAP_ADMIN_NOT_POOL: u32 = 1 << 1;
+    /// The LP mint is not the pool mint of the swap.
+    pub const LP_MINT_MISMATCH: u32 = 1 << 2;
+    /// A fee account is not an admin fee account of the swap.
+    pub const FEE_ACCOUNT_MISMATCH: u32 = 1 << 3;
+    /// A fee account is not owned by the [super::Pool].
+    pub const FEE_ACCOUNT_OWNER: u32 = 1 << 4;
+    /// A fee account does not hold the mint of its side of the swap.
+    pub const FEE_ACCOUNT_MINT: u32 = 1 << 5;
+    /// A fee account has a delegate.
+    pub const FEE_ACCOUNT_DELEGATE: u32 = 1 << 6;
+    /// A fee account has a close authority.
+    pub const FEE_ACCOUNT_CLOSE_AUTHORITY: u32 = 1 << 7;
+    /// The amp factor is outside of the permissionless amp factor bounds.
+    pub const AMP_OUT_OF_RANGE: u32 = 1 << 8;
+    /// The fees of the swap are not [super::PoolManager::initial_fees].
+    pub const FEES_MISMATCH: u32 = 1 << 9;
+    /// Both tokens of the swap have the same mint.
+    pub const MINTS_EQUAL: u32 = 1 << 10;
+    // 1 << 11 was reported for unsorted mints, which are now accepted. It is left unused
+    // so that the other bits keep their meaning.
+    /// A token mint or reserve account does not match the swap.
+    pub const TOKEN_ACCOUNT_MISMATCH: u32 = 1 << 12;
+    /// The pool account is not the [super::Pool] address of the swap.
+    pub const POOL_ADDRESS_MISMATCH: u32 = 1 << 13;
+    /// The [super::Pool] already exists.
+    pub const POOL_EXISTS: u32 = 1 << 14;
+
+    /// Failed [super::import_checks] enabled on the [super::PoolManager]
+    /// are reported shifted left by this many bits.
+    pub const IMPORT_CHECKS_SHIFT: u32 = 16;
+}
+
+/// Roles on a [PoolManager] which may be given to an account via a [RoleGrant].
+/// The admin implicitly holds every role.
+pub mod roles {
+    /// May set and schedule fees on swaps.
+    pub const FEE_SETTER: u16 = 1 << 0;
+    /// May ramp the amplification coefficient of swaps.
+    pub const RAMPER: u16 = 1 << 1;
+    /// May pause and unpause swaps.
+    pub const PAUSER: u16 = 1 << 2;
+    /// May import pools as the operator.
+    pub const IMPORTER: u16 = 1 << 3;
+    /// May recover tokens sent to pools.
+    pub const SWEEPER: u16 = 1 << 4;
+    /// May set the beneficiary.
+    pub const BENEFICIARY_MANAGER: u16 = 1 << 5;
+
+    /// All roles.
+    pub const ALL: u16 = FEE_SETTER | RAMPER | PAUSER | IMPORTER | SWEEPER | BENEFICIARY_MANAGER;
+
+    /// Returns true if the bitmask only contains known roles.
+    pub fn are_valid(roles: u16) -> bool {
+        roles & !ALL == 0
+    }
+}
+
+/// Roles on a [PoolManager] given to an account.
+#[account]
+#[derive(Copy, Default, Debug)]
+pub struct RoleGrant {
+    /// The [PoolManager].
+    pub manager: Pubkey,
+    /// The account which holds the roles.
+    pub grantee: Pubkey,
+    /// Bump seed
+    pub bump: u8,
+    /// Bitmask of [roles].
+    pub roles: u16,
+    /// Time after which the roles may no longer be used.
+    pub valid_until: i64,
+}
+
+impl RoleGrant {
+    /// Number of bytes in a serialized [RoleGrant].
+    pub const LEN: usize = PUBKEY_BYTES * 2 + 1 + 2 + 8;
+
+    /// Returns true if every role in the bitmask has been granted and has not expired.
+    pub fn has_roles(&self, roles: u16, now: i64) -> bool {
+        self.roles & roles == roles && now <= self.valid_until
+    }
+}
+
+/// Maximum number of approvers in an [ApprovalConfig].
+pub const MAX_APPROVERS: usize = 16;
+
+/// Built-in M-of-N approval configuration of a [PoolManager].
+///
+/// Once enough approvers have approved a [Proposal], it may be executed,
+/// signing as the admin of the [PoolManager].
+#[account]
+#[derive(Default, Debug)]
+pub struct ApprovalConfig {
+    /// The [PoolManager].
+    pub manager: Pubkey,
+    /// Bump seed
+    pub bump: u8,
+
+    /// Number of approvals required to execute a [Proposal].
+    pub threshold: u8,
+    /// Sequence number of the approver set.
+    /// Changing the approvers invalidates all pending [Proposal]s.
+    pub approver_set_seqno: u32,
+    /// Total number of [Proposal]s created.
+    pub num_proposals: u64,
+    /// Accounts which may create and approve [Proposal]s.
+    pub approvers: Vec<Pubkey>,
+}
+
+impl ApprovalConfig {
+    /// Number of bytes in a serialized [ApprovalConfig] with [MAX_APPROVERS] approvers.
+    pub const LEN: usize = PUBKEY_BYTES + 1 + 1 + 4 + 8 + 4 + PUBKEY_BYTES * MAX_APPROVERS;
+
+    /// Index of the approver in [ApprovalConfig::approvers], if any.
+    pub fn approver_index(&self, key: &Pubkey) -> Option<usize> {
+        self.approvers.iter().position(|approver| approver == key)
+    }
+}
+
+/// An instruction to [crate::pools] which executes as the admin of
+/// a [PoolManager] once enough approvals have been collected.
+#[account]
+#[derive(Default, Debug)]
+pub struct Proposal {
+    /// The [PoolManager].
+    pub manager: Pubkey,
+    /// Proposal index, 0-indexed.
+    pub index: u64,
+    /// Bump seed
+    pub bump: u8,
+
+    /// Account which created the proposal.
+    pub proposer: Pubkey,
+    /// [ApprovalConfig::approver_set_seqno] at the time of creation.
+    pub approver_set_seqno: u32,
+    /// Bitmask of the indices in [ApprovalConfig::approvers] which approved.
+    pub approvals: u16,
+
+    /// The instruction to execute.
+    pub instruction: ProposalInstruction,
+
+    /// Time the proposal was created.
+    pub created_at: i64,
+    /// Time the proposal was executed. Zero if not executed.
+    pub executed_at: i64,
+}
+
+impl Proposal {
+    /// Number of bytes in a serialized [Proposal] containing the given instruction.
+    pub fn space(instruction: &ProposalInstruction) -> usize {
+        PUBKEY_BYTES + 8 + 1 + PUBKEY_BYTES + 4 + 2 + instruction.space() + 8 + 8
+    }
+
+    /// Number of approvals collected.
+    pub fn num_approvals(&self) -> u32 {
+        self.approvals.count_ones()
+    }
+}
+
+/// An instruction to [crate::pools] stored in a [Proposal].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default, Debug, PartialEq, Eq)]
+pub struct ProposalInstruction {
+    /// Accounts passed to the instruction.
+    pub keys: Vec<ProposalAccountMeta>,
+    /// Instruction data.
+    pub data: Vec<u8>,
+}
+
+impl ProposalInstruction {
+    /// Number of bytes in a serialized [ProposalInstruction].
+    pub fn space(&self) -> usize {
+        4 + ProposalAccountMeta::LEN * self.keys.len() + 4 + self.data.len()
+    }
+}
+
+/// An account passed to a [ProposalInstruction].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct ProposalAccountMeta {
+    /// Key of the account.
+    pub pubkey: Pubkey,
+    /// True if the account must sign.
+    pub is_signer: bool,
+    /// True if the account is writable.
+    pub is_writable: bool,
+}
+
+impl ProposalAccountMeta {
+    /// Number of bytes in a serialized [ProposalAccountMeta].
+    pub const LEN: usize = PUBKEY_BYTES + 1 + 1;
+}
+
+impl From<&ProposalAccountMeta> for AccountMeta {
+    fn from(meta: &ProposalAccountMeta) -> Self {
+        AccountMeta {
+            pubkey: meta.pubkey,
+            is_signer: meta.is_signer,
+            is_writable: meta.is_writable,
+        }
+    }
+}
+
+/// Upper bounds on the fees of swaps managed by a [PoolManager].
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, Debug, PartialEq, Eq)]
+pub struct FeeCaps {
+    /// Maximum trade fee, in basis points.
+    pub max_trade_fee_bps: u16,
+    /// Maximum withdraw fee, in basis points.
+    pub max_withdraw_fee_bps: u16,
+    /// Maximum share of the trade and withdraw fees which may go to the admin, in basis points.
+    pub max_admin_fee_share_bps: u16,
+}
+
+impl FeeCaps {
+    /// Number of bytes in a serialized [FeeCaps].
+    pub const LEN: usize = 2 * 3;
+
+    /// Caps set on new [PoolManager]s.
+    pub const DEFAULT: FeeCaps = FeeCaps {
+        max_trade_fee_bps: 100,
+        max_withdraw_fee_bps: 100,
+        max_admin_fee_share_bps: 5_000,
+    };
+
+    /// Returns true if every cap is at most 100%.
+    pub fn is_valid(&self) -> bool {
+        self.max_trade_fee_bps <= BPS_DENOMINATOR
+            && self.max_withdraw_fee_bps <= BPS_DENOMINATOR
+            && self.max_admin_fee_share_bps <= BPS_DENOMINATOR
+    }
+
+    /// Returns true if the [SwapFees] do not exceed any of the caps.
+    pub fn allows(&self, fees: &SwapFees) -> bool {
+        fraction_within_bps(
+            fees.trade_fee_numerator,
+            fees.trade_fee_denominator,
+            self.max_trade_fee_bps,
+        ) && fraction_within_bps(
+            fees.withdraw_fee_numerator,
+            fees.withdraw_fee_denominator,
+            self.max_withdraw_fee_bps,
+        ) && fraction_within_bps(
+            fees.admin_trade_fee_numerator,
+            fees.admin_trade_fee_denominator,
+            self.max_admin_fee_share_bps,
+        ) && fraction_within_bps(
+            fees.admin_withdraw_fee_numerator,
+            fees.admin_withdraw_fee_denominator,
+            self.max_admin_fee_share_bps,
+        )
+    }
+}
+
+/// Number of basis points in 100%.
+const BPS_DENOMINATOR: u16 = 10_000;
+
+/// Returns true if `numerator / denominator` is at most `max_bps` basis points.
+/// Fractions with a zero denominator are never within bounds.
+fn fraction_within_bps(numerator: u64, denominator: u64, max_bps: u16) -> bool {
+    denominator != 0
+        && (numerator as u128) * (BPS_DENOMINATOR as u128)
+            <= (max_bps as u128) * (denominator as u128)
 }
 
 /// Gets the sorted mints of the [Pool].
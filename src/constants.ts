@@ -1,5 +1,6 @@
 import { buildCoderMap } from "@saberhq/anchor-contrib";
 import { PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
 
 import { PoolsJSON } from "./idls/pools";
 import type { PoolsProgram, PoolsTypes } from "./types";
@@ -17,6 +18,75 @@ export const POOLS_IDLS = {
   Pools: PoolsJSON,
 };
 
+/**
+ * Expiry timestamp for operators and role grants which never expire (`i64::MAX`).
+ */
+export const NO_EXPIRY = new BN("9223372036854775807");
+
+/**
+ * Parameters of a pool manager which may be locked via `lockParams`.
+ * Locked parameters can never be changed again.
+ */
+export const PARAMS = {
+  INITIAL_FEES: 1 << 0,
+  AMP_BOUNDS: 1 << 1,
+  BENEFICIARY: 1 << 2,
+  OPERATOR: 1 << 3,
+  POOL_FEES: 1 << 4,
+  POOL_RAMPS: 1 << 5,
+  SWAP_ADMIN: 1 << 6,
+  ALL: (1 << 7) - 1,
+} as const;
+
+/**
+ * Roles which may be given to an account via `createRoleGrant`.
+ */
+export const ROLES = {
+  FEE_SETTER: 1 << 0,
+  RAMPER: 1 << 1,
+  PAUSER: 1 << 2,
+  IMPORTER: 1 << 3,
+  SWEEPER: 1 << 4,
+  BENEFICIARY_MANAGER: 1 << 5,
+  ALL: (1 << 6) - 1,
+} as const;
+
+/**
+ * Optional checks on permissionless imports, enabled via `setImportChecks`.
+ */
+export const IMPORT_CHECKS = {
+  NO_MINT_FREEZE_AUTHORITY: 1 << 0,
+  LP_MINT_AUTHORITY_IS_SWAP: 1 << 1,
+  NO_LP_FREEZE_AUTHORITY: 1 << 2,
+  NOT_PAUSED: 1 << 3,
+  NO_RAMP_IN_PROGRESS: 1 << 4,
+  NONZERO_RESERVES: 1 << 5,
+  NO_PENDING_ADMIN: 1 << 6,
+  ALL: (1 << 7) - 1,
+} as const;
+
+/**
+ * Failures reported by `checkImport`. Failed `IMPORT_CHECKS` are reported
+ * shifted left by `IMPORT_CHECKS_SHIFT` bits.
+ */
+export const IMPORT_FAILURES = {
+  IMPORTS_DISABLED: 1 << 0,
+  SWAP_ADMIN_NOT_POOL: 1 << 1,
+  LP_MINT_MISMATCH: 1 << 2,
+  FEE_ACCOUNT_MISMATCH: 1 << 3,
+  FEE_ACCOUNT_OWNER: 1 << 4,
+  FEE_ACCOUNT_MINT: 1 << 5,
+  FEE_ACCOUNT_DELEGATE: 1 << 6,
+  FEE_ACCOUNT_CLOSE_AUTHORITY: 1 << 7,
+  AMP_OUT_OF_RANGE: 1 << 8,
+  FEES_MISMATCH: 1 << 9,
+  MINTS_EQUAL: 1 << 10,
+  TOKEN_ACCOUNT_MISMATCH: 1 << 12,
+  POOL_ADDRESS_MISMATCH: 1 << 13,
+  POOL_EXISTS: 1 << 14,
+  IMPORT_CHECKS_SHIFT: 16,
+} as const;
+
 // Matches Curve's MIN_RAMP_DURATION.
 export const MIN_RAMP_DURATION = 86_400;
 
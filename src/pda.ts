@@ -1,3 +1,4 @@
+import type { BN } from "@project-serum/anchor";
 import { utils } from "@project-serum/anchor";
 import { PublicKey } from "@solana/web3.js";
 
@@ -38,3 +39,109 @@ export const findSaberPoolSorted = async (
     POOLS_ADDRESSES.Pools
   );
 };
+
+export const findRoleGrant = async (
+  poolManager: PublicKey,
+  grantee: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("SaberRoleGrant"),
+      poolManager.toBytes(),
+      grantee.toBytes(),
+    ],
+    POOLS_ADDRESSES.Pools
+  );
+};
+
+/**
+ * Synchronous version of {@link findRoleGrant}.
+ */
+export const getRoleGrantAddress = (
+  poolManager: PublicKey,
+  grantee: PublicKey
+): PublicKey => {
+  const [key] = utils.publicKey.findProgramAddressSync(
+    [
+      utils.bytes.utf8.encode("SaberRoleGrant"),
+      poolManager.toBytes(),
+      grantee.toBytes(),
+    ],
+    POOLS_ADDRESSES.Pools
+  );
+  return key;
+};
+
+export const findAmpSchedule = async (
+  pool: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [utils.bytes.utf8.encode("SaberAmpSchedule"), pool.toBytes()],
+    POOLS_ADDRESSES.Pools
+  );
+};
+
+export const findMintIncident = async (
+  poolManager: PublicKey,
+  index: BN
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("SaberMintIncident"),
+      poolManager.toBytes(),
+      index.toArrayLike(Buffer, "le", 8),
+    ],
+    POOLS_ADDRESSES.Pools
+  );
+};
+
+export const findTimelockedAction = async (
+  poolManager: PublicKey,
+  index: BN
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("SaberTimelockedAction"),
+      poolManager.toBytes(),
+      index.toArrayLike(Buffer, "le", 8),
+    ],
+    POOLS_ADDRESSES.Pools
+  );
+};
+
+export const findApprovalConfig = async (
+  poolManager: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [utils.bytes.utf8.encode("SaberApprovalConfig"), poolManager.toBytes()],
+    POOLS_ADDRESSES.Pools
+  );
+};
+
+export const findProposal = async (
+  poolManager: PublicKey,
+  index: BN
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("SaberProposal"),
+      poolManager.toBytes(),
+      index.toArrayLike(Buffer, "le", 8),
+    ],
+    POOLS_ADDRESSES.Pools
+  );
+};
+
+export const findMintOracle = async (
+  poolManager: PublicKey,
+  mint: PublicKey
+): Promise<[PublicKey, number]> => {
+  return await PublicKey.findProgramAddress(
+    [
+      utils.bytes.utf8.encode("SaberMintOracle"),
+      poolManager.toBytes(),
+      mint.toBytes(),
+    ],
+    POOLS_ADDRESSES.Pools
+  );
+};
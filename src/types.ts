@@ -16,6 +16,11 @@ export type PoolsTypes = AnchorTypes<
 >;
 
 export type SwapFees = PoolsTypes["Defined"]["SwapFees"];
+export type FeeCaps = PoolsTypes["Defined"]["FeeCaps"];
+export type RampPolicy = PoolsTypes["Defined"]["RampPolicy"];
+export type AmpScheduleStep = PoolsTypes["Defined"]["AmpScheduleStep"];
+export type TimelockedActionKind =
+  PoolsTypes["Defined"]["TimelockedActionKind"];
 
 type pmAccounts = PoolsTypes["Accounts"];
 export type PoolData = pmAccounts["Pool"];
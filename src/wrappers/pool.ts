@@ -1,11 +1,29 @@
 import type { TransactionEnvelope } from "@saberhq/solana-contrib";
 import type { Fees, StableSwapState } from "@saberhq/stableswap-sdk";
 import { SWAP_PROGRAM_ID } from "@saberhq/stableswap-sdk";
-import { getOrCreateATAs, TOKEN_PROGRAM_ID, u64 } from "@saberhq/token-utils";
-import type { PublicKey, TransactionInstruction } from "@solana/web3.js";
+import {
+  getOrCreateATA,
+  getOrCreateATAs,
+  TOKEN_PROGRAM_ID,
+  u64,
+} from "@saberhq/token-utils";
+import type {
+  PublicKey,
+  Signer,
+  TransactionInstruction,
+} from "@solana/web3.js";
+import { SystemProgram } from "@solana/web3.js";
+import type BN from "bn.js";
 
+import { NO_EXPIRY } from "../constants";
+import { findAmpSchedule, getRoleGrantAddress } from "../pda";
 import type { PoolManagerSDK } from "../poolManagerSdk";
-import type { PoolData, PoolsProgram, SwapFees } from "../types";
+import type {
+  AmpScheduleStep,
+  PoolData,
+  PoolsProgram,
+  SwapFees,
+} from "../types";
 
 /**
  * Wrapper class for Pool. Methods operate on the object's Pool and Swap.
@@ -26,6 +44,19 @@ export class PoolWrapper {
     this.program = sdk.programs.Pools;
   }
 
+  /**
+   * Loads this {@link PoolWrapper} with the given signer as the authority of its
+   * instructions, e.g. the guardian, a pool admin or the holder of a role.
+   */
+  withSigner(signer: Signer): PoolWrapper {
+    return new PoolWrapper(
+      this.sdk.withSigner(signer),
+      this.key,
+      this.data,
+      signer.publicKey
+    );
+  }
+
   rampA(targetAmp: u64, stopRampTs: number): TransactionEnvelope {
     const instruction = this.program.instruction.rampA(
       targetAmp,
@@ -50,9 +81,103 @@ export class PoolWrapper {
     return this.sdk.newTx([instruction]);
   }
 
-  pauseSwap(): TransactionEnvelope {
+  /**
+   * Ramps the amplification coefficient as the creator of a permissionlessly
+   * imported pool, who must be the authority of this wrapper.
+   */
+  creatorRampA(targetAmp: u64, stopRampTs: number): TransactionEnvelope {
+    const instruction = this.program.instruction.creatorRampA(
+      targetAmp,
+      new u64(stopRampTs),
+      {
+        accounts: this._getCreatorAccounts(),
+      }
+    );
+
+    return this.sdk.newTx([instruction]);
+  }
+
+  /**
+   * Stops ramping the amplification coefficient as the creator of the pool.
+   */
+  creatorStopRampA(): TransactionEnvelope {
+    const instruction = this.program.instruction.creatorStopRampA({
+      accounts: this._getCreatorAccounts(),
+    });
+
+    return this.sdk.newTx([instruction]);
+  }
+
+  /**
+   * Creates the amp schedule of the pool, as the admin or the operator.
+   */
+  async createAmpSchedule(steps: AmpScheduleStep[]): Promise<{
+    ampSchedule: PublicKey;
+    tx: TransactionEnvelope;
+  }> {
+    const [ampSchedule] = await findAmpSchedule(this.key);
+    const instruction = this.program.instruction.createAmpSchedule(steps, {
+      accounts: {
+        poolManager: this.data.manager,
+        pool: this.key,
+        ampSchedule,
+        authority: this.admin,
+        payer: this.sdk.provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      },
+    });
+
+    return { ampSchedule, tx: this.sdk.newTx([instruction]) };
+  }
+
+  /**
+   * Replaces the steps of the amp schedule, starting again from the first step.
+   */
+  async setAmpSchedule(
+    steps: AmpScheduleStep[]
+  ): Promise<TransactionEnvelope> {
+    const [ampSchedule] = await findAmpSchedule(this.key);
+    const instruction = this.program.instruction.setAmpSchedule(steps, {
+      accounts: {
+        poolManager: this.data.manager,
+        pool: this.key,
+        ampSchedule,
+        authority: this.admin,
+      },
+    });
+
+    return this.sdk.newTx([instruction]);
+  }
+
+  /**
+   * Starts the next ramp of the amp schedule once the current ramp has ended.
+   * Anyone may call this.
+   */
+  async advanceAmpSchedule(): Promise<TransactionEnvelope> {
+    const [ampSchedule] = await findAmpSchedule(this.key);
+    const instruction = this.program.instruction.advanceAmpSchedule({
+      accounts: {
+        poolManager: this.data.manager,
+        swap: this.data.swap,
+        pool: this.key,
+        ampSchedule,
+        swapProgram: SWAP_PROGRAM_ID,
+      },
+    });
+
+    return this.sdk.newTx([instruction]);
+  }
+
+  pauseSwap(authority: PublicKey = this.admin): TransactionEnvelope {
     const instruction = this.program.instruction.pauseSwap({
-      accounts: this._getCommonAccounts(),
+      accounts: {
+        poolManager: this.data.manager,
+        pool: this.key,
+        swap: this.data.swap,
+        swapProgram: SWAP_PROGRAM_ID,
+        authority,
+        roleGrant: getRoleGrantAddress(this.data.manager, authority),
+      },
     });
 
     return this.sdk.newTx([instruction]);
@@ -69,19 +194,29 @@ export class PoolWrapper {
   commitNewAdmin(newAdmin: PublicKey): TransactionEnvelope {
     const instruction = this.program.instruction.commitNewAdmin({
       accounts: {
-        ...this._getCommonAccounts(),
+        poolManager: this.data.manager,
+        swap: this.data.swap,
+        pool: this.key,
+        admin: this.admin,
         newAdmin,
+        swapProgram: SWAP_PROGRAM_ID,
       },
     });
 
     return this.sdk.newTx([instruction]);
   }
 
-  applyNewAdmin(): TransactionEnvelope {
+  /**
+   * Hands the swap over to the committed admin once the change delay has passed.
+   */
+  applyNewAdmin(newAdmin: PublicKey): TransactionEnvelope {
     const instruction = this.program.instruction.applyNewAdmin({
       accounts: {
         ...this._getCommonAccounts(),
       },
+      remainingAccounts: [
+        { pubkey: newAdmin, isSigner: false, isWritable: false },
+      ],
     });
 
     return this.sdk.newTx([instruction]);
@@ -89,31 +224,156 @@ export class PoolWrapper {
 
   setNewFees(newFees: Fees): TransactionEnvelope {
     return this.sdk.newTx([
-      this.program.instruction.setNewFees(encodeFees(newFees), {
+      this.program.instruction.setNewFees(encodeSwapFees(newFees), {
         accounts: this._getCommonAccounts(),
       }),
     ]);
   }
 
-  setBeneficiary(newBeneficiary: PublicKey): TransactionEnvelope {
+  /**
+   * Schedules new fees, which may be applied by anyone once effective.
+   */
+  scheduleNewFees(newFees: Fees, effectiveTs: number): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.scheduleNewFees(
+        encodeSwapFees(newFees),
+        new u64(effectiveTs),
+        {
+          accounts: {
+            poolManager: this.data.manager,
+            pool: this.key,
+            authority: this.admin,
+            roleGrant: getRoleGrantAddress(this.data.manager, this.admin),
+          },
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Cancels the fees scheduled via {@link scheduleNewFees}.
+   */
+  cancelScheduledFees(): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.cancelScheduledFees({
+        accounts: {
+          poolManager: this.data.manager,
+          pool: this.key,
+          authority: this.admin,
+          roleGrant: getRoleGrantAddress(this.data.manager, this.admin),
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Sets the scheduled fees on the swap once they are effective. Anyone may call this.
+   */
+  applyScheduledFees(): TransactionEnvelope {
     return this.sdk.newTx([
-      this.program.instruction.setBeneficiary({
+      this.program.instruction.applyScheduledFees({
+        accounts: {
+          poolManager: this.data.manager,
+          swap: this.data.swap,
+          pool: this.key,
+          swapProgram: SWAP_PROGRAM_ID,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Commits a new beneficiary, who must then accept.
+   * If `force` is set, the beneficiary is set without accepting. Only use this in emergencies.
+   */
+  setBeneficiary(
+    newBeneficiary: PublicKey,
+    force = false
+  ): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.setBeneficiary(force, {
+        accounts: this._getBeneficiaryAccounts(newBeneficiary),
+      }),
+    ]);
+  }
+
+  acceptBeneficiary(beneficiary: PublicKey): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.acceptBeneficiary({
+        accounts: {
+          poolManager: this.data.manager,
+          beneficiary,
+        },
+      }),
+    ]);
+  }
+
+  setGuardian(newGuardian: PublicKey): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.setGuardian({
         accounts: {
           poolManager: this.data.manager,
           admin: this.admin,
-          beneficiary: newBeneficiary,
+          guardian: newGuardian,
         },
       }),
     ]);
   }
 
-  setOperator(newOperator: PublicKey): TransactionEnvelope {
+  setPoolAdmin(poolAdmin: PublicKey): TransactionEnvelope {
     return this.sdk.newTx([
-      this.program.instruction.setOperator({
+      this.program.instruction.setPoolAdmin({
         accounts: {
           poolManager: this.data.manager,
+          pool: this.key,
           admin: this.admin,
-          operator: newOperator,
+          poolAdmin,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Commits a new operator, who must then accept.
+   * If `force` is set, the operator is set without accepting. Only use this in emergencies.
+   */
+  setOperator(
+    newOperator: PublicKey,
+    validUntil: BN = NO_EXPIRY,
+    force = false
+  ): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.setOperator(validUntil, force, {
+        accounts: this._getOperatorAccounts(newOperator),
+      }),
+    ]);
+  }
+
+  acceptOperator(operator: PublicKey): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.acceptOperator({
+        accounts: {
+          poolManager: this.data.manager,
+          operator,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Pauses the swap if its reserves are more imbalanced than the manager allows.
+   * Anyone may call this.
+   */
+  tripCircuitBreaker(swapState: StableSwapState): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.tripCircuitBreaker({
+        accounts: {
+          poolManager: this.data.manager,
+          swap: this.data.swap,
+          pool: this.key,
+          reserveA: swapState.tokenA.reserve,
+          reserveB: swapState.tokenB.reserve,
+          swapProgram: SWAP_PROGRAM_ID,
         },
       }),
     ]);
@@ -165,18 +425,111 @@ export class PoolWrapper {
     return this.sdk.newTx(allInstructions);
   }
 
+  /**
+   * Sends the tokens of a stray token account owned by the pool
+   * to the ATA of the beneficiary, as the admin or a sweeper.
+   */
+  async recoverTokens(
+    tokenAccount: PublicKey,
+    mint: PublicKey
+  ): Promise<TransactionEnvelope> {
+    const { beneficiary } =
+      await this.sdk.programs.Pools.account.poolManager.fetch(
+        this.data.manager
+      );
+    const { address: beneficiaryAccount, instruction } = await getOrCreateATA({
+      provider: this.sdk.provider,
+      mint,
+      owner: beneficiary,
+    });
+
+    const allInstructions: TransactionInstruction[] = [];
+    if (instruction) {
+      allInstructions.push(instruction);
+    }
+    allInstructions.push(
+      this.program.instruction.recoverTokens({
+        accounts: {
+          poolManager: this.data.manager,
+          pool: this.key,
+          swap: this.data.swap,
+          authority: this.admin,
+          roleGrant: getRoleGrantAddress(this.data.manager, this.admin),
+          tokenAccount,
+          beneficiaryAccount,
+          tokenProgram: TOKEN_PROGRAM_ID,
+        },
+      })
+    );
+
+    return this.sdk.newTx(allInstructions);
+  }
+
+  /**
+   * Sends the lamports of the pool above its rent-exempt minimum
+   * to the beneficiary, as the admin or a sweeper.
+   */
+  async recoverLamports(): Promise<TransactionEnvelope> {
+    const { beneficiary } =
+      await this.sdk.programs.Pools.account.poolManager.fetch(
+        this.data.manager
+      );
+    return this.sdk.newTx([
+      this.program.instruction.recoverLamports({
+        accounts: {
+          poolManager: this.data.manager,
+          pool: this.key,
+          authority: this.admin,
+          roleGrant: getRoleGrantAddress(this.data.manager, this.admin),
+          beneficiary,
+        },
+      }),
+    ]);
+  }
+
+  private _getBeneficiaryAccounts(beneficiary: PublicKey) {
+    return {
+      poolManager: this.data.manager,
+      authority: this.admin,
+      roleGrant: getRoleGrantAddress(this.data.manager, this.admin),
+      beneficiary,
+    };
+  }
+
+  private _getOperatorAccounts(operator: PublicKey) {
+    return {
+      poolManager: this.data.manager,
+      admin: this.admin,
+      operator,
+    };
+  }
+
+  private _getCreatorAccounts() {
+    return {
+      poolManager: this.data.manager,
+      swap: this.data.swap,
+      pool: this.key,
+      swapProgram: SWAP_PROGRAM_ID,
+      creator: this.admin,
+    };
+  }
+
   private _getCommonAccounts() {
     return {
       poolManager: this.data.manager,
       pool: this.key,
       swap: this.data.swap,
       swapProgram: SWAP_PROGRAM_ID,
-      admin: this.admin,
+      authority: this.admin,
+      roleGrant: getRoleGrantAddress(this.data.manager, this.admin),
     };
   }
 }
 
-const encodeFees = (fees: Fees): SwapFees => ({
+/**
+ * Encodes fees in the layout of the program.
+ */
+export const encodeSwapFees = (fees: Fees): SwapFees => ({
   adminTradeFeeNumerator: new u64(fees.adminTrade.numerator.toString()),
   adminTradeFeeDenominator: new u64(fees.adminTrade.denominator.toString()),
   adminWithdrawFeeNumerator: new u64(fees.adminWithdraw.numerator.toString()),
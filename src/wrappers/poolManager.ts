@@ -16,18 +16,31 @@ import {
   getOrCreateATAs,
   Percent,
   TOKEN_PROGRAM_ID,
+  u64,
 } from "@saberhq/token-utils";
 import type {
+  AccountMeta,
   PublicKey,
   Signer,
   TransactionInstruction,
 } from "@solana/web3.js";
 import { Keypair, SystemProgram } from "@solana/web3.js";
+import type BN from "bn.js";
 import invariant from "tiny-invariant";
 
-import { findSaberPool, findSaberPoolManager } from "../pda";
+import { NO_EXPIRY } from "../constants";
+import {
+  findApprovalConfig,
+  findMintIncident,
+  findMintOracle,
+  findSaberPool,
+  findSaberPoolManager,
+  findTimelockedAction,
+  getRoleGrantAddress,
+} from "../pda";
 import type { PoolManagerSDK } from "../poolManagerSdk";
 import type {
+  FeeCaps,
   PendingPool,
   PendingPoolManagerWrapper,
   PendingStableSwap,
@@ -35,10 +48,12 @@ import type {
   PoolManagerData,
   PoolManagerWrapperCtorArgs,
   PoolsProgram,
+  RampPolicy,
   StableSwapCtorArgs,
+  TimelockedActionKind,
 } from "../types";
 import { comparePubkeys } from "../utils/comparePubkeys";
-import { PoolWrapper } from "./pool";
+import { encodeSwapFees, PoolWrapper } from "./pool";
 
 type TokenAccounts = {
   fees: PublicKey;
@@ -158,6 +173,635 @@ export class PoolManagerWrapper {
     return this.loadPoolWrapper(poolKey);
   }
 
+  /**
+   * Sets the minimum fee notice period. Lowering the notice period only takes effect
+   * once applied via {@link PoolManagerWrapper#applyMinFeeNoticePeriod}.
+   */
+  setMinFeeNoticePeriod(minFeeNoticePeriod: number): TransactionEnvelope {
+    return new TransactionEnvelope(this.provider, [
+      this.program.instruction.setMinFeeNoticePeriod(
+        new u64(minFeeNoticePeriod),
+        {
+          accounts: {
+            poolManager: this.key,
+            admin: this.provider.wallet.publicKey,
+          },
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Sets the delay between committing and applying a new swap admin.
+   * The delay may only be lowered before the first pool is created.
+   */
+  setSwapAdminChangeDelay(swapAdminChangeDelay: number): TransactionEnvelope {
+    return new TransactionEnvelope(this.provider, [
+      this.program.instruction.setSwapAdminChangeDelay(
+        new u64(swapAdminChangeDelay),
+        {
+          accounts: {
+            poolManager: this.key,
+            admin: this.provider.wallet.publicKey,
+          },
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Applies a pending minimum fee notice period.
+   */
+  applyMinFeeNoticePeriod(): TransactionEnvelope {
+    return new TransactionEnvelope(this.provider, [
+      this.program.instruction.applyMinFeeNoticePeriod({
+        accounts: {
+          poolManager: this.key,
+          admin: this.provider.wallet.publicKey,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Commits new fee caps, which may be applied after the fee caps change delay.
+   */
+  commitNewFeeCaps(feeCaps: FeeCaps): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.commitNewFeeCaps(feeCaps, {
+        accounts: {
+          poolManager: this.key,
+          admin: this.provider.wallet.publicKey,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Applies the pending fee caps.
+   */
+  applyNewFeeCaps(): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.applyNewFeeCaps({
+        accounts: {
+          poolManager: this.key,
+          admin: this.provider.wallet.publicKey,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Enables or disables importing pools. The guardian may only disable imports.
+   */
+  setImportsEnabled(
+    permissionlessImportsEnabled: boolean,
+    operatorImportsEnabled: boolean,
+    authority: PublicKey = this.provider.wallet.publicKey
+  ): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.setImportsEnabled(
+        permissionlessImportsEnabled,
+        operatorImportsEnabled,
+        {
+          accounts: {
+            poolManager: this.key,
+            authority,
+          },
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Sets the optional checks required of permissionlessly imported pools.
+   */
+  setImportChecks(checks: number): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.setImportChecks(checks, {
+        accounts: {
+          poolManager: this.key,
+          admin: this.provider.wallet.publicKey,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Commits a new ramp policy, which may be applied after the ramp policy
+   * change delay. Before the first pool exists it takes effect immediately.
+   */
+  commitNewRampPolicy(rampPolicy: RampPolicy): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.commitNewRampPolicy(rampPolicy, {
+        accounts: {
+          poolManager: this.key,
+          admin: this.provider.wallet.publicKey,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Applies the pending ramp policy.
+   */
+  applyNewRampPolicy(): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.applyNewRampPolicy({
+        accounts: {
+          poolManager: this.key,
+          admin: this.provider.wallet.publicKey,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Sets the timelock delay. Once the timelock is enabled,
+   * the delay may only be changed through the timelock.
+   */
+  setTimelockDelay(timelockDelay: number): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.setTimelockDelay(new u64(timelockDelay), {
+        accounts: {
+          poolManager: this.key,
+          admin: this.provider.wallet.publicKey,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Queues a timelocked action at the next index of the manager.
+   */
+  async queueTimelockedAction(action: TimelockedActionKind): Promise<{
+    timelockedAction: PublicKey;
+    tx: TransactionEnvelope;
+  }> {
+    const { numTimelockedActions } = await this.reloadData();
+    const [timelockedAction] = await findTimelockedAction(
+      this.key,
+      numTimelockedActions
+    );
+    return {
+      timelockedAction,
+      tx: this.sdk.newTx([
+        this.program.instruction.queueTimelockedAction(action, {
+          accounts: {
+            poolManager: this.key,
+            timelockedAction,
+            admin: this.provider.wallet.publicKey,
+            payer: this.provider.wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+          },
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Executes a timelocked action which modifies the manager.
+   */
+  executeTimelockedManagerAction(
+    timelockedAction: PublicKey
+  ): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.executeTimelockedManagerAction({
+        accounts: {
+          poolManager: this.key,
+          timelockedAction,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Executes a timelocked action which modifies the swap of a pool.
+   */
+  async executeTimelockedSwapAction(
+    timelockedAction: PublicKey,
+    pool: PublicKey
+  ): Promise<TransactionEnvelope> {
+    const { swap } = await this.loadPool(pool);
+    return this.sdk.newTx([
+      this.program.instruction.executeTimelockedSwapAction({
+        accounts: {
+          poolManager: this.key,
+          timelockedAction,
+          swap,
+          pool,
+          swapProgram: SWAP_PROGRAM_ID,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Cancels a timelocked action.
+   * This may be called by the admin or the guardian.
+   */
+  cancelTimelockedAction(timelockedAction: PublicKey): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.cancelTimelockedAction({
+        accounts: {
+          poolManager: this.key,
+          timelockedAction,
+          adminOrGuardian: this.provider.wallet.publicKey,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Sets the maximum reserve imbalance before the circuit breaker
+   * may be tripped.
+   */
+  setMaxImbalanceBps(maxImbalanceBps: number): TransactionEnvelope {
+    return this.sdk.newTx([this.setMaxImbalanceBpsIx(maxImbalanceBps)]);
+  }
+
+  /**
+   * Creates the instruction to set the maximum reserve imbalance,
+   * signed by the given admin. This may be used in a proposal.
+   */
+  setMaxImbalanceBpsIx(
+    maxImbalanceBps: number,
+    admin: PublicKey = this.provider.wallet.publicKey
+  ): TransactionInstruction {
+    return this.program.instruction.setMaxImbalanceBps(maxImbalanceBps, {
+      accounts: {
+        poolManager: this.key,
+        admin,
+      },
+    });
+  }
+
+  /**
+   * Enables M-of-N approvals.
+   * From then on, only executed proposals may act as the admin.
+   */
+  async enableApprovals(
+    approvers: PublicKey[],
+    threshold: number
+  ): Promise<TransactionEnvelope> {
+    const [approvalConfig] = await findApprovalConfig(this.key);
+    return this.sdk.newTx([
+      this.program.instruction.enableApprovals(approvers, threshold, {
+        accounts: {
+          poolManager: this.key,
+          approvalConfig,
+          admin: this.provider.wallet.publicKey,
+          payer: this.provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Creates a proposal to execute an instruction of the Pools program
+   * as the admin. The instruction must be signed by the proposal,
+   * which is found via {@link findProposal}.
+   */
+  async createProposal(
+    proposal: PublicKey,
+    instruction: TransactionInstruction
+  ): Promise<TransactionEnvelope> {
+    invariant(
+      instruction.programId.equals(this.program.programId),
+      "proposals may only execute instructions of the Pools program"
+    );
+    const [approvalConfig] = await findApprovalConfig(this.key);
+    return this.sdk.newTx([
+      this.program.instruction.createProposal(
+        {
+          keys: instruction.keys,
+          data: instruction.data,
+        },
+        {
+          accounts: {
+            approvalConfig,
+            proposal,
+            proposer: this.provider.wallet.publicKey,
+            payer: this.provider.wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+          },
+        }
+      ),
+    ]);
+  }
+
+  /**
+   * Approves a proposal.
+   */
+  async approveProposal(proposal: PublicKey): Promise<TransactionEnvelope> {
+    const [approvalConfig] = await findApprovalConfig(this.key);
+    return this.sdk.newTx([
+      this.program.instruction.approveProposal({
+        accounts: {
+          approvalConfig,
+          proposal,
+          approver: this.provider.wallet.publicKey,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Executes a proposal which has enough approvals.
+   */
+  async executeProposal(proposal: PublicKey): Promise<TransactionEnvelope> {
+    const [approvalConfig] = await findApprovalConfig(this.key);
+    const { instruction } = await this.program.account.proposal.fetch(proposal);
+    return this.sdk.newTx([
+      this.program.instruction.executeProposal({
+        accounts: {
+          approvalConfig,
+          proposal,
+        },
+        // the proposal signs via the program, not the transaction
+        remainingAccounts: instruction.keys
+          .filter(({ pubkey }) => !pubkey.equals(proposal))
+          .map(({ pubkey, isWritable }) => ({
+            pubkey,
+            isSigner: false,
+            isWritable,
+          })),
+      }),
+    ]);
+  }
+
+  /**
+   * Locks parameters of the manager. See {@link PARAMS}.
+   */
+  lockParams(lockedParams: number): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.lockParams(lockedParams, {
+        accounts: {
+          poolManager: this.key,
+          admin: this.provider.wallet.publicKey,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Permanently gives up the admin of the manager.
+   */
+  renounceAdmin(): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.renounceAdmin({
+        accounts: {
+          poolManager: this.key,
+          admin: this.provider.wallet.publicKey,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Creates the oracle of a mint, used to pause its swaps once it depegs.
+   */
+  async createMintOracle({
+    mint,
+    priceAccount,
+    pegPrice,
+    pegExpo,
+    maxDeviationBps,
+    maxStalenessSlots,
+  }: {
+    mint: PublicKey;
+    priceAccount: PublicKey;
+    pegPrice: BN;
+    pegExpo: number;
+    maxDeviationBps: number;
+    maxStalenessSlots: BN;
+  }): Promise<{ mintOracle: PublicKey; tx: TransactionEnvelope }> {
+    const [mintOracle] = await findMintOracle(this.key, mint);
+    return {
+      mintOracle,
+      tx: this.sdk.newTx([
+        this.program.instruction.createMintOracle(
+          pegPrice,
+          pegExpo,
+          maxDeviationBps,
+          maxStalenessSlots,
+          {
+            accounts: {
+              poolManager: this.key,
+              mint,
+              mintOracle,
+              priceAccount,
+              admin: this.provider.wallet.publicKey,
+              payer: this.provider.wallet.publicKey,
+              systemProgram: SystemProgram.programId,
+            },
+          }
+        ),
+      ]),
+    };
+  }
+
+  /**
+   * Pauses the given pools holding a mint if the mint has depegged.
+   * Anyone may call this.
+   */
+  async checkDepeg(
+    mint: PublicKey,
+    pools: PoolWrapper[]
+  ): Promise<TransactionEnvelope> {
+    const [mintOracle] = await findMintOracle(this.key, mint);
+    const { priceAccount } = await this.program.account.mintOracle.fetch(
+      mintOracle
+    );
+    return this.sdk.newTx([
+      this.program.instruction.checkDepeg({
+        accounts: {
+          poolManager: this.key,
+          mintOracle,
+          priceAccount,
+          swapProgram: SWAP_PROGRAM_ID,
+        },
+        remainingAccounts: poolSwapPairs(pools),
+      }),
+    ]);
+  }
+
+  /**
+   * Pauses every given pool holding the mint and records a mint incident.
+   * This may be called by the admin or the guardian.
+   */
+  async pauseByMint(
+    mint: PublicKey,
+    pools: PoolWrapper[],
+    authority: PublicKey = this.provider.wallet.publicKey
+  ): Promise<{ mintIncident: PublicKey; tx: TransactionEnvelope }> {
+    const { numMintIncidents } = await this.reloadData();
+    const [mintIncident] = await findMintIncident(this.key, numMintIncidents);
+    return {
+      mintIncident,
+      tx: this.sdk.newTx([
+        this.program.instruction.pauseByMint({
+          accounts: {
+            poolManager: this.key,
+            mint,
+            mintIncident,
+            authority,
+            payer: this.provider.wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+            swapProgram: SWAP_PROGRAM_ID,
+          },
+          remainingAccounts: poolSwapPairs(pools),
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Grants roles on the manager to an account until `validUntil`.
+   */
+  createRoleGrant(
+    grantee: PublicKey,
+    roles: number,
+    validUntil: BN = NO_EXPIRY
+  ): { roleGrant: PublicKey; tx: TransactionEnvelope } {
+    const roleGrant = getRoleGrantAddress(this.key, grantee);
+    return {
+      roleGrant,
+      tx: this.sdk.newTx([
+        this.program.instruction.createRoleGrant(roles, validUntil, {
+          accounts: {
+            poolManager: this.key,
+            roleGrant,
+            grantee,
+            admin: this.provider.wallet.publicKey,
+            payer: this.provider.wallet.publicKey,
+            systemProgram: SystemProgram.programId,
+          },
+        }),
+      ]),
+    };
+  }
+
+  /**
+   * Sets the roles of an account and when they expire.
+   */
+  setRoles(
+    grantee: PublicKey,
+    roles: number,
+    validUntil: BN = NO_EXPIRY
+  ): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.setRoles(roles, validUntil, {
+        accounts: {
+          poolManager: this.key,
+          roleGrant: getRoleGrantAddress(this.key, grantee),
+          admin: this.provider.wallet.publicKey,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Revokes every role of an account.
+   */
+  revokeRoleGrant(grantee: PublicKey): TransactionEnvelope {
+    return this.setRoles(grantee, 0);
+  }
+
+  /**
+   * Pauses many swaps at once.
+   */
+  pauseSwaps(
+    pools: PoolWrapper[],
+    authority: PublicKey = this.provider.wallet.publicKey
+  ): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.pauseSwaps({
+        accounts: this._batchSwapAccounts(authority),
+        remainingAccounts: poolSwapPairs(pools),
+      }),
+    ]);
+  }
+
+  /**
+   * Unpauses many swaps at once.
+   */
+  unpauseSwaps(
+    pools: PoolWrapper[],
+    authority: PublicKey = this.provider.wallet.publicKey
+  ): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.unpauseSwaps({
+        accounts: this._batchSwapAccounts(authority),
+        remainingAccounts: poolSwapPairs(pools),
+      }),
+    ]);
+  }
+
+  /**
+   * Sets the same new fees on many swaps at once.
+   */
+  setNewFeesBatch(
+    pools: PoolWrapper[],
+    newFees: Fees,
+    authority: PublicKey = this.provider.wallet.publicKey
+  ): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.setNewFeesBatch(encodeSwapFees(newFees), {
+        accounts: this._batchSwapAccounts(authority),
+        remainingAccounts: poolSwapPairs(pools),
+      }),
+    ]);
+  }
+
+  /**
+   * Ramps the amplification coefficient of many swaps to the same target at once.
+   */
+  rampABatch(
+    pools: PoolWrapper[],
+    targetAmp: u64,
+    stopRampTs: number,
+    authority: PublicKey = this.provider.wallet.publicKey
+  ): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.rampABatch(targetAmp, new u64(stopRampTs), {
+        accounts: this._batchSwapAccounts(authority),
+        remainingAccounts: poolSwapPairs(pools),
+      }),
+    ]);
+  }
+
+  /**
+   * Migrates the manager to the current layout.
+   */
+  migrate(): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.migratePoolManager({
+        accounts: {
+          poolManager: this.key,
+          payer: this.provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        },
+      }),
+    ]);
+  }
+
+  /**
+   * Migrates a pool of the manager to the current layout.
+   */
+  migratePool(pool: PublicKey): TransactionEnvelope {
+    return this.sdk.newTx([
+      this.program.instruction.migratePool({
+        accounts: {
+          pool,
+          payer: this.provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        },
+      }),
+    ]);
+  }
+
   /**
    * reloadData
    */
@@ -186,6 +830,31 @@ export class PoolManagerWrapper {
     });
   }
 
+  /**
+   * Loads the fees new swaps must be created with.
+   */
+  async loadInitialFees(): Promise<Fees> {
+    const { initialFees } = await this.reloadData();
+    return {
+      trade: new Percent(
+        initialFees.tradeFeeNumerator,
+        initialFees.tradeFeeDenominator
+      ),
+      withdraw: new Percent(
+        initialFees.withdrawFeeNumerator,
+        initialFees.withdrawFeeDenominator
+      ),
+      adminTrade: new Percent(
+        initialFees.adminTradeFeeNumerator,
+        initialFees.adminTradeFeeDenominator
+      ),
+      adminWithdraw: new Percent(
+        initialFees.adminWithdrawFeeNumerator,
+        initialFees.adminWithdrawFeeDenominator
+      ),
+    };
+  }
+
   /**
    * Initializes a new StableSwap, assuming the inputs are sorted.
    */
@@ -208,26 +877,7 @@ export class PoolManagerWrapper {
     }
 
     const { provider } = this.sdk;
-    const pmData = await this.reloadData();
-    const initialFeesRaw = pmData.initialFees;
-    const initialFees: Fees = {
-      trade: new Percent(
-        initialFeesRaw.tradeFeeNumerator,
-        initialFeesRaw.tradeFeeDenominator
-      ),
-      withdraw: new Percent(
-        initialFeesRaw.withdrawFeeNumerator,
-        initialFeesRaw.withdrawFeeDenominator
-      ),
-      adminTrade: new Percent(
-        initialFeesRaw.adminTradeFeeNumerator,
-        initialFeesRaw.adminTradeFeeDenominator
-      ),
-      adminWithdraw: new Percent(
-        initialFeesRaw.adminWithdrawFeeNumerator,
-        initialFeesRaw.adminWithdrawFeeDenominator
-      ),
-    };
+    const initialFees = await this.loadInitialFees();
 
     const allInstructions: TransactionInstruction[] = [];
     let destinationPoolTokenAccount = outputLp;
@@ -314,8 +964,16 @@ export class PoolManagerWrapper {
           swapAccountSigner.publicKey,
           pool,
           mintLP,
-          feeAccounts.accounts.tokenA,
-          feeAccounts.accounts.tokenB
+          {
+            mint: accounts.mintA.mint,
+            reserve: accounts.mintA.reserve,
+            fees: feeAccounts.accounts.tokenA,
+          },
+          {
+            mint: accounts.mintB.mint,
+            reserve: accounts.mintB.reserve,
+            fees: feeAccounts.accounts.tokenB,
+          }
         ),
       })
     );
@@ -334,7 +992,7 @@ export class PoolManagerWrapper {
    */
   async importPoolAsOperator(
     swapAccount: PublicKey,
-    adminOrOperator: PublicKey = this.sdk.provider.wallet.publicKey
+    authority: PublicKey = this.sdk.provider.wallet.publicKey
   ): Promise<PendingPool> {
     const { config, state } = await StableSwap.load(
       this.program.provider.connection,
@@ -379,13 +1037,22 @@ export class PoolManagerWrapper {
       }),
       this.program.instruction.importPoolAsOperator(bump, {
         accounts: {
-          adminOrOperator,
+          authority,
+          roleGrant: getRoleGrantAddress(this.key, authority),
           importPool: this._importPoolAccounts(
             swapAccount,
             pool,
             state.poolTokenMint,
-            accounts.mintA,
-            accounts.mintB
+            {
+              mint: state.tokenA.mint,
+              reserve: state.tokenA.reserve,
+              fees: accounts.mintA,
+            },
+            {
+              mint: state.tokenB.mint,
+              reserve: state.tokenB.reserve,
+              fees: accounts.mintB,
+            }
           ),
         },
       }),
@@ -398,6 +1065,153 @@ export class PoolManagerWrapper {
     };
   }
 
+  /**
+   * Points the fee accounts of a swap to the Pool and commits the Pool as its new admin,
+   * so that the pool may then be adopted via {@link adoptPool}.
+   * Must be signed by the admin of the swap.
+   */
+  async commitPoolAdoption(
+    swapAccount: PublicKey
+  ): Promise<{ poolKey: PublicKey; tx: TransactionEnvelope }> {
+    const { config, state } = await StableSwap.load(
+      this.program.provider.connection,
+      swapAccount
+    );
+    const [pool] = await findSaberPool(
+      this.key,
+      state.tokenA.mint,
+      state.tokenB.mint
+    );
+    const { accounts, instructions } = await getOrCreateATAs({
+      provider: this.sdk.provider,
+      mints: {
+        mintA: state.tokenA.mint,
+        mintB: state.tokenB.mint,
+      },
+      owner: pool,
+    });
+
+    const tx = this.sdk.newTx([
+      ...instructions,
+      createAdminSetFeeAccountInstruction({
+        config,
+        state,
+        tokenAccount: accounts.mintA,
+      }),
+      createAdminSetFeeAccountInstruction({
+        config,
+        state,
+        tokenAccount: accounts.mintB,
+      }),
+      createAdminCommitNewAdminInstruction({
+        config,
+        state,
+        newAdminAccount: pool,
+      }),
+    ]);
+    return { poolKey: pool, tx };
+  }
+
+  /**
+   * Imports a swap whose admin committed the Pool as its new admin
+   * via {@link commitPoolAdoption}. The previous admin must sign.
+   */
+  async adoptPool(
+    swapAccount: PublicKey,
+    previousAdmin: PublicKey = this.sdk.provider.wallet.publicKey
+  ): Promise<PendingPool> {
+    const { state } = await StableSwap.load(
+      this.program.provider.connection,
+      swapAccount
+    );
+    const [pool, bump] = await findSaberPool(
+      this.key,
+      state.tokenA.mint,
+      state.tokenB.mint
+    );
+
+    const tx = this.sdk.newTx([
+      this.program.instruction.adoptPool(bump, {
+        accounts: {
+          importPool: this._importPoolAccounts(
+            swapAccount,
+            pool,
+            state.poolTokenMint,
+            {
+              mint: state.tokenA.mint,
+              reserve: state.tokenA.reserve,
+              fees: state.tokenA.adminFeeAccount,
+            },
+            {
+              mint: state.tokenB.mint,
+              reserve: state.tokenB.reserve,
+              fees: state.tokenB.adminFeeAccount,
+            }
+          ),
+          swap: swapAccount,
+          previousAdmin,
+          swapProgram: SWAP_PROGRAM_ID,
+        },
+      }),
+    ]);
+
+    return {
+      poolBump: bump,
+      poolKey: pool,
+      tx,
+    };
+  }
+
+  /**
+   * Runs every check of a permissionless import of the swap without importing it.
+   * The bitmask of `IMPORT_FAILURES` is set as the return data.
+   */
+  async checkImport(swapAccount: PublicKey): Promise<TransactionEnvelope> {
+    const { state } = await StableSwap.load(
+      this.program.provider.connection,
+      swapAccount
+    );
+    const [pool] = await findSaberPool(
+      this.key,
+      state.tokenA.mint,
+      state.tokenB.mint
+    );
+
+    return this.sdk.newTx([
+      this.program.instruction.checkImport({
+        accounts: this._importPoolAccounts(
+          swapAccount,
+          pool,
+          state.poolTokenMint,
+          {
+            mint: state.tokenA.mint,
+            reserve: state.tokenA.reserve,
+            fees: state.tokenA.adminFeeAccount,
+          },
+          {
+            mint: state.tokenB.mint,
+            reserve: state.tokenB.reserve,
+            fees: state.tokenB.adminFeeAccount,
+          }
+        ),
+      }),
+    ]);
+  }
+
+  /**
+   * Simulates {@link checkImport}, returning the bitmask of `IMPORT_FAILURES`.
+   * Zero means the swap may be imported permissionlessly.
+   */
+  async diagnoseImport(swapAccount: PublicKey): Promise<number> {
+    const tx = await this.checkImport(swapAccount);
+    const { value } = await tx.simulate();
+    const prefix = `Program return: ${this.program.programId.toString()} `;
+    const returnLog = value.logs?.find((log) => log.startsWith(prefix));
+    invariant(returnLog, "check_import did not return data");
+    const data = Buffer.from(returnLog.slice(prefix.length), "base64");
+    return data.readUInt32LE(0);
+  }
+
   private async _initFeeAndReserveTokenAccounts({
     mintA,
     reserveA,
@@ -438,12 +1252,21 @@ export class PoolManagerWrapper {
     };
   }
 
+  private _batchSwapAccounts(authority: PublicKey) {
+    return {
+      poolManager: this.key,
+      swapProgram: SWAP_PROGRAM_ID,
+      authority,
+      roleGrant: getRoleGrantAddress(this.key, authority),
+    };
+  }
+
   private _importPoolAccounts(
     swap: PublicKey,
     pool: PublicKey,
     lpMint: PublicKey,
-    tokenAFees: PublicKey,
-    tokenBFees: PublicKey
+    tokenA: TokenAccounts,
+    tokenB: TokenAccounts
   ): {
     poolManager: PublicKey;
     swap: PublicKey;
@@ -451,6 +1274,10 @@ export class PoolManagerWrapper {
     lpMint: PublicKey;
     tokenAFees: PublicKey;
     tokenBFees: PublicKey;
+    tokenAMint: PublicKey;
+    tokenBMint: PublicKey;
+    tokenAReserve: PublicKey;
+    tokenBReserve: PublicKey;
     payer: PublicKey;
     systemProgram: PublicKey;
   } {
@@ -459,10 +1286,23 @@ export class PoolManagerWrapper {
       swap,
       pool,
       lpMint,
-      tokenAFees,
-      tokenBFees,
+      tokenAFees: tokenA.fees,
+      tokenBFees: tokenB.fees,
+      tokenAMint: tokenA.mint,
+      tokenBMint: tokenB.mint,
+      tokenAReserve: tokenA.reserve,
+      tokenBReserve: tokenB.reserve,
       payer: this.provider.wallet.publicKey,
       systemProgram: SystemProgram.programId,
     };
   }
 }
+
+/**
+ * The `(pool, swap)` pairs passed to instructions operating on many pools.
+ */
+const poolSwapPairs = (pools: PoolWrapper[]): AccountMeta[] =>
+  pools.flatMap(({ key, data }) => [
+    { pubkey: key, isSigner: false, isWritable: true },
+    { pubkey: data.swap, isSigner: false, isWritable: true },
+  ]);
@@ -0,0 +1,124 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { StableSwap } from "@saberhq/stableswap-sdk";
+import { u64 } from "@saberhq/token-utils";
+import type { PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import { expect } from "chai";
+
+import { MIN_RAMP_DURATION } from "../src/constants";
+import type { AmpScheduleStep } from "../src/types";
+import type { PoolWrapper } from "../src/wrappers/pool";
+import { makePoolManagerSDK } from "./testutils";
+import { createFundedKeypair, setupManagerWithPool } from "./workspace";
+
+describe("Saber Pool Manager amp schedules", () => {
+  const sdk = makePoolManagerSDK();
+  const { provider } = sdk;
+
+  let poolWrapper: PoolWrapper;
+
+  beforeEach(async () => {
+    ({ poolWrapper } = await setupManagerWithPool(sdk));
+  });
+
+  const now = () => Math.floor(Date.now() / 1_000);
+  const step = (targetAmp: number, stopRampTs: number): AmpScheduleStep => ({
+    targetAmp: new u64(targetAmp),
+    stopRampTs: new BN(stopRampTs),
+  });
+
+  async function loadTargetAmpFactor() {
+    const { state } = await StableSwap.load(
+      provider.connection,
+      poolWrapper.data.swap
+    );
+    return state.targetAmpFactor.toNumber();
+  }
+
+  async function loadAmpSchedule(ampSchedule: PublicKey) {
+    return await sdk.programs.Pools.account.ampSchedule.fetch(ampSchedule);
+  }
+
+  it("Anyone may advance an amp schedule one step at a time", async () => {
+    const steps = [
+      step(200, now() + MIN_RAMP_DURATION + 60),
+      step(400, now() + 3 * MIN_RAMP_DURATION),
+    ];
+    const { ampSchedule, tx } = await poolWrapper.createAmpSchedule(steps);
+    await expectTX(tx, "Create amp schedule").to.be.fulfilled;
+    let schedule = await loadAmpSchedule(ampSchedule);
+    expect(schedule.pool).eqAddress(poolWrapper.key);
+    expect(schedule.nextStep).to.equal(0);
+    expect(schedule.steps).to.have.lengthOf(2);
+
+    const crank = poolWrapper.withSigner(await createFundedKeypair(sdk));
+    await expectTX(
+      await crank.advanceAmpSchedule(),
+      "Advance amp schedule"
+    ).to.be.fulfilled;
+    expect(await loadTargetAmpFactor()).to.equal(200);
+    schedule = await loadAmpSchedule(ampSchedule);
+    expect(schedule.nextStep).to.equal(1);
+
+    // the next step may only start once the current ramp has ended
+    await expectTX(
+      await crank.advanceAmpSchedule(),
+      "Advance during a ramp"
+    ).to.be.rejected;
+    expect(await loadTargetAmpFactor()).to.equal(200);
+  });
+
+  it("Completed amp schedules may not be advanced", async () => {
+    const { ampSchedule, tx } = await poolWrapper.createAmpSchedule([
+      step(200, now() + MIN_RAMP_DURATION + 60),
+    ]);
+    await expectTX(tx, "Create amp schedule").to.be.fulfilled;
+    await expectTX(
+      await poolWrapper.setAmpSchedule([]),
+      "Clear amp schedule"
+    ).to.be.fulfilled;
+    expect((await loadAmpSchedule(ampSchedule)).steps).to.be.empty;
+
+    await expectTX(
+      await poolWrapper.advanceAmpSchedule(),
+      "Advance completed amp schedule"
+    ).to.be.rejected;
+  });
+
+  it("Only the admin or operator may set amp schedules", async () => {
+    const outsider = await createFundedKeypair(sdk);
+    const { tx: outsiderTx } = await poolWrapper
+      .withSigner(outsider)
+      .createAmpSchedule([step(200, now() + MIN_RAMP_DURATION + 60)]);
+    await expectTX(outsiderTx, "Create amp schedule as outsider").to.be
+      .rejected;
+
+    const operator = await createFundedKeypair(sdk);
+    await expectTX(
+      poolWrapper.setOperator(operator.publicKey, undefined, true),
+      "Set operator"
+    ).to.be.fulfilled;
+    const operatorPool = poolWrapper.withSigner(operator);
+    const { tx } = await operatorPool.createAmpSchedule([
+      step(200, now() + MIN_RAMP_DURATION + 60),
+    ]);
+    await expectTX(tx, "Create amp schedule as operator").to.be.fulfilled;
+
+    await expectTX(
+      await poolWrapper
+        .withSigner(outsider)
+        .setAmpSchedule([step(1_000, now() + MIN_RAMP_DURATION + 60)]),
+      "Set amp schedule as outsider"
+    ).to.be.rejected;
+    // steps must end in order
+    await expectTX(
+      await operatorPool.setAmpSchedule([
+        step(200, now() + 3 * MIN_RAMP_DURATION),
+        step(400, now() + MIN_RAMP_DURATION + 60),
+      ]),
+      "Set unordered amp schedule"
+    ).to.be.rejected;
+  });
+});
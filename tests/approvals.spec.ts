@@ -0,0 +1,109 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import type { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+
+import { findApprovalConfig, findProposal } from "../src/pda";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { makePoolManagerSDK } from "./testutils";
+import { createFundedKeypair, setupManagerWithPool } from "./workspace";
+
+describe("Saber Pool Manager approvals", () => {
+  const sdk = makePoolManagerSDK();
+
+  let admin: Keypair;
+  let approver: Keypair;
+  let pmWrapper: PoolManagerWrapper;
+  let adminManager: PoolManagerWrapper;
+
+  beforeEach(async () => {
+    ({ admin, pmWrapper, adminManager } = await setupManagerWithPool(sdk));
+    approver = await createFundedKeypair(sdk);
+
+    await expectTX(
+      await adminManager.enableApprovals(
+        [admin.publicKey, approver.publicKey],
+        2
+      ),
+      "Enable approvals"
+    ).to.be.fulfilled;
+  });
+
+  it("Approvals were enabled", async () => {
+    const manager = await pmWrapper.reloadData();
+    expect(manager.approvalsRequired).to.be.true;
+
+    const [approvalConfigKey] = await findApprovalConfig(pmWrapper.key);
+    const approvalConfig =
+      await sdk.programs.Pools.account.approvalConfig.fetch(approvalConfigKey);
+    expect(approvalConfig.manager).eqAddress(pmWrapper.key);
+    expect(approvalConfig.threshold).to.equal(2);
+    expect(approvalConfig.approvers).to.have.length(2);
+  });
+
+  it("Admin key may no longer act as admin", async () => {
+    await expectTX(
+      adminManager.setMaxImbalanceBps(1_234),
+      "Set max imbalance as admin"
+    ).to.be.rejected;
+  });
+
+  it("Executed proposal acts as admin", async () => {
+    const [approvalConfigKey] = await findApprovalConfig(pmWrapper.key);
+    const { numProposals } =
+      await sdk.programs.Pools.account.approvalConfig.fetch(approvalConfigKey);
+    const [proposal] = await findProposal(pmWrapper.key, numProposals);
+
+    await expectTX(
+      await adminManager.createProposal(
+        proposal,
+        pmWrapper.setMaxImbalanceBpsIx(1_234, proposal)
+      ),
+      "Create proposal"
+    ).to.be.fulfilled;
+
+    // the proposer approves, but the threshold is not met yet
+    await expectTX(
+      await pmWrapper.executeProposal(proposal),
+      "Execute without enough approvals"
+    ).to.be.rejected;
+
+    await expectTX(
+      await pmWrapper.withSigner(approver).approveProposal(proposal),
+      "Approve proposal"
+    ).to.be.fulfilled;
+    await expectTX(
+      await pmWrapper.executeProposal(proposal),
+      "Execute proposal"
+    ).to.be.fulfilled;
+
+    const manager = await pmWrapper.reloadData();
+    expect(manager.maxImbalanceBps).to.equal(1_234);
+    const executed = await sdk.programs.Pools.account.proposal.fetch(proposal);
+    expect(executed.executedAt.toNumber()).to.be.greaterThan(0);
+
+    await expectTX(
+      await pmWrapper.executeProposal(proposal),
+      "Execute proposal twice"
+    ).to.be.rejected;
+  });
+
+  it("Only approvers may create proposals", async () => {
+    const outsider = await createFundedKeypair(sdk);
+
+    const [approvalConfigKey] = await findApprovalConfig(pmWrapper.key);
+    const { numProposals } =
+      await sdk.programs.Pools.account.approvalConfig.fetch(approvalConfigKey);
+    const [proposal] = await findProposal(pmWrapper.key, numProposals);
+    await expectTX(
+      await pmWrapper
+        .withSigner(outsider)
+        .createProposal(
+          proposal,
+          pmWrapper.setMaxImbalanceBpsIx(1_234, proposal)
+        ),
+      "Create proposal as outsider"
+    ).to.be.rejected;
+  });
+});
@@ -0,0 +1,108 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { RECOMMENDED_FEES, StableSwap } from "@saberhq/stableswap-sdk";
+import { u64 } from "@saberhq/token-utils";
+import type { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+
+import { MIN_RAMP_DURATION } from "../src/constants";
+import type { PoolWrapper } from "../src/wrappers/pool";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { createPool, makePoolManagerSDK } from "./testutils";
+import { setupManagerWithPool } from "./workspace";
+
+describe("Saber Pool Manager batch operations", () => {
+  const sdk = makePoolManagerSDK();
+  const { provider } = sdk;
+
+  let minter: Keypair;
+  let pmWrapper: PoolManagerWrapper;
+  let adminManager: PoolManagerWrapper;
+  let pools: PoolWrapper[];
+
+  beforeEach(async () => {
+    let poolWrapper: PoolWrapper;
+    ({ minter, pmWrapper, adminManager, poolWrapper } =
+      await setupManagerWithPool(sdk, { minFeeNoticePeriod: 0 }));
+    const { pool } = await createPool(
+      provider,
+      minter,
+      pmWrapper,
+      new u64(100)
+    );
+    pools = [poolWrapper, await adminManager.loadPoolWrapper(pool)];
+  });
+
+  async function loadSwaps() {
+    const swaps = await Promise.all(
+      pools.map(({ data }) => StableSwap.load(provider.connection, data.swap))
+    );
+    return swaps.map(({ state }) => state);
+  }
+
+  it("Pause and unpause many swaps", async () => {
+    // the provider wallet is not the admin
+    await expectTX(pmWrapper.pauseSwaps(pools), "Pause swaps as outsider").to
+      .be.rejected;
+
+    await expectTX(adminManager.pauseSwaps(pools), "Pause swaps").to.be
+      .fulfilled;
+    for (const state of await loadSwaps()) {
+      expect(state.isPaused).to.be.true;
+    }
+
+    await expectTX(adminManager.unpauseSwaps(pools), "Unpause swaps").to.be
+      .fulfilled;
+    for (const state of await loadSwaps()) {
+      expect(state.isPaused).to.be.false;
+    }
+  });
+
+  it("Set new fees on many swaps", async () => {
+    await expectTX(
+      adminManager.setNewFeesBatch(pools, RECOMMENDED_FEES),
+      "Set new fees"
+    ).to.be.fulfilled;
+    for (const state of await loadSwaps()) {
+      expect(state.fees.trade.numerator.toString()).to.equal(
+        RECOMMENDED_FEES.trade.numerator.toString()
+      );
+    }
+  });
+
+  it("Ramp many swaps", async () => {
+    const stopRampTs = Math.floor(Date.now() / 1_000) + MIN_RAMP_DURATION + 60;
+    await expectTX(
+      adminManager.rampABatch(pools, new u64(200), stopRampTs),
+      "Ramp swaps"
+    ).to.be.fulfilled;
+    for (const state of await loadSwaps()) {
+      expect(state.targetAmpFactor.toString()).to.equal("200");
+    }
+  });
+
+  it("Batch accounts must come in pairs", async () => {
+    await expectTX(adminManager.pauseSwaps([]), "Pause no swaps").to.be
+      .rejected;
+
+    const tx = adminManager.pauseSwaps(pools);
+    // drop the swap of the last pair
+    tx.instructions[0]?.keys.pop();
+    await expectTX(tx, "Pause an odd number of accounts").to.be.rejected;
+    for (const state of await loadSwaps()) {
+      expect(state.isPaused).to.be.false;
+    }
+  });
+
+  it("Batch may not include pools of another manager", async () => {
+    const { poolWrapper: otherPool } = await setupManagerWithPool(sdk);
+    await expectTX(
+      adminManager.pauseSwaps([...pools, otherPool]),
+      "Pause a pool of another manager"
+    ).to.be.rejected;
+    for (const state of await loadSwaps()) {
+      expect(state.isPaused).to.be.false;
+    }
+  });
+});
@@ -0,0 +1,96 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { createAdminApplyNewAdminInstruction } from "@saberhq/stableswap-sdk";
+import { u64 } from "@saberhq/token-utils";
+import type { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+
+import { IMPORT_CHECKS, IMPORT_FAILURES } from "../src/constants";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { makePoolManagerSDK } from "./testutils";
+import { deploySwap, setupManagerWithPool } from "./workspace";
+
+describe("Saber Pool Manager import diagnostics", () => {
+  const sdk = makePoolManagerSDK();
+  const { provider } = sdk;
+
+  let minter: Keypair;
+  let pmWrapper: PoolManagerWrapper;
+  let adminManager: PoolManagerWrapper;
+  let swapAccount: PublicKey;
+
+  beforeEach(async () => {
+    ({ minter, pmWrapper, adminManager, swapAccount } =
+      await setupManagerWithPool(sdk));
+  });
+
+  /**
+   * Deploys a swap administered by the minter and hands it over to its Pool,
+   * so that it passes every check of a permissionless import.
+   */
+  async function deployImportableSwap() {
+    const { swap } = await deploySwap(provider, minter, {
+      ampFactor: new u64(100),
+      fees: await pmWrapper.loadInitialFees(),
+    });
+    const { tx } = await pmWrapper.commitPoolAdoption(swap.config.swapAccount);
+    tx.instructions.push(
+      createAdminApplyNewAdminInstruction({
+        config: swap.config,
+        state: swap.state,
+      })
+    );
+    tx.addSigners(minter);
+    await expectTX(tx, "Hand the swap over to its pool").to.be.fulfilled;
+    return swap.config.swapAccount;
+  }
+
+  it("Importable swaps report no failures", async () => {
+    await expectTX(
+      adminManager.setImportChecks(IMPORT_CHECKS.NO_PENDING_ADMIN),
+      "Set import checks"
+    ).to.be.fulfilled;
+    const importable = await deployImportableSwap();
+    expect(await pmWrapper.diagnoseImport(importable)).to.equal(0);
+  });
+
+  it("Every failed check is reported", async () => {
+    // administered by the minter, with an amp factor above the bounds
+    const { swap } = await deploySwap(provider, minter, {
+      ampFactor: new u64(1_000),
+    });
+    const failures = await pmWrapper.diagnoseImport(swap.config.swapAccount);
+    expect(failures & IMPORT_FAILURES.SWAP_ADMIN_NOT_POOL).to.not.equal(0);
+    expect(failures & IMPORT_FAILURES.FEE_ACCOUNT_OWNER).to.not.equal(0);
+    expect(failures & IMPORT_FAILURES.AMP_OUT_OF_RANGE).to.not.equal(0);
+    expect(failures & IMPORT_FAILURES.POOL_EXISTS).to.equal(0);
+
+    // enabled import checks are reported shifted
+    await expectTX(
+      adminManager.setImportChecks(IMPORT_CHECKS.NO_PENDING_ADMIN),
+      "Set import checks"
+    ).to.be.fulfilled;
+    const { tx } = await pmWrapper.commitPoolAdoption(swap.config.swapAccount);
+    tx.addSigners(minter);
+    await expectTX(tx, "Commit pool adoption").to.be.fulfilled;
+    const pendingAdminFailure =
+      IMPORT_CHECKS.NO_PENDING_ADMIN << IMPORT_FAILURES.IMPORT_CHECKS_SHIFT;
+    expect(
+      (await pmWrapper.diagnoseImport(swap.config.swapAccount)) &
+        pendingAdminFailure
+    ).to.equal(pendingAdminFailure);
+
+    // the swap of the existing pool may not be imported again
+    expect(await pmWrapper.diagnoseImport(swapAccount)).to.equal(
+      IMPORT_FAILURES.POOL_EXISTS
+    );
+    await expectTX(
+      adminManager.setImportsEnabled(false, true),
+      "Disable permissionless imports"
+    ).to.be.fulfilled;
+    expect(await pmWrapper.diagnoseImport(swapAccount)).to.equal(
+      IMPORT_FAILURES.POOL_EXISTS | IMPORT_FAILURES.IMPORTS_DISABLED
+    );
+  });
+});
@@ -0,0 +1,130 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { TransactionEnvelope } from "@saberhq/solana-contrib";
+import { StableSwap } from "@saberhq/stableswap-sdk";
+import { SPLToken, TOKEN_PROGRAM_ID, u64 } from "@saberhq/token-utils";
+import type { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+
+import type { PoolWrapper } from "../src/wrappers/pool";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { makePoolManagerSDK } from "./testutils";
+import { createFundedKeypair, setupManagerWithPool } from "./workspace";
+
+describe("Saber Pool Manager circuit breaker", () => {
+  const sdk = makePoolManagerSDK();
+  const { provider } = sdk;
+
+  let minter: Keypair;
+  let pmWrapper: PoolManagerWrapper;
+  let adminManager: PoolManagerWrapper;
+  let poolWrapper: PoolWrapper;
+
+  beforeEach(async () => {
+    ({ minter, pmWrapper, adminManager, poolWrapper } =
+      await setupManagerWithPool(sdk));
+  });
+
+  async function loadSwap() {
+    return await StableSwap.load(provider.connection, poolWrapper.data.swap);
+  }
+
+  /**
+   * Mints tokens directly into reserve A, imbalancing the swap.
+   */
+  async function imbalanceReserves(amount: u64) {
+    const { state } = await loadSwap();
+    await expectTX(
+      new TransactionEnvelope(
+        provider,
+        [
+          SPLToken.createMintToInstruction(
+            TOKEN_PROGRAM_ID,
+            state.tokenA.mint,
+            state.tokenA.reserve,
+            minter.publicKey,
+            [],
+            amount
+          ),
+        ],
+        [minter]
+      ),
+      "Imbalance reserves"
+    ).to.be.fulfilled;
+  }
+
+  it("Circuit breaker is disabled by default", async () => {
+    await imbalanceReserves(new u64(1_000_000));
+    const { state } = await loadSwap();
+    await expectTX(
+      poolWrapper.tripCircuitBreaker(state),
+      "Trip disabled circuit breaker"
+    ).to.be.rejected;
+  });
+
+  it("Trip circuit breaker on imbalanced reserves", async () => {
+    await expectTX(
+      adminManager.setMaxImbalanceBps(100),
+      "Set max imbalance"
+    ).to.be.fulfilled;
+    expect((await pmWrapper.reloadData()).maxImbalanceBps).to.equal(100);
+
+    // balanced reserves are within the threshold
+    let { state } = await loadSwap();
+    await expectTX(
+      poolWrapper.tripCircuitBreaker(state),
+      "Trip on balanced reserves"
+    ).to.be.rejected;
+
+    // 10:1 reserves are far outside the threshold, even when amplified
+    await imbalanceReserves(new u64(9_000_000));
+    ({ state } = await loadSwap());
+
+    // anyone may trip the circuit breaker
+    const outsider = await createFundedKeypair(sdk);
+    const outsiderPool = await pmWrapper
+      .withSigner(outsider)
+      .loadPoolWrapper(poolWrapper.key);
+    await expectTX(
+      outsiderPool.tripCircuitBreaker(state),
+      "Trip circuit breaker"
+    ).to.be.fulfilled;
+
+    ({ state } = await loadSwap());
+    expect(state.isPaused).to.be.true;
+    const pool = await pmWrapper.loadPool(poolWrapper.key);
+    expect(pool.circuitBreakerTripped).to.be.true;
+
+    // a paused swap may not be tripped again
+    await expectTX(
+      poolWrapper.tripCircuitBreaker(state),
+      "Trip paused swap"
+    ).to.be.rejected;
+
+    await expectTX(poolWrapper.unpauseSwap(), "Unpause as admin").to.be
+      .fulfilled;
+    const unpaused = await pmWrapper.loadPool(poolWrapper.key);
+    expect(unpaused.circuitBreakerTripped).to.be.false;
+  });
+
+  it("Circuit breaker may not trip once the admin is renounced", async () => {
+    await expectTX(
+      adminManager.setMaxImbalanceBps(100),
+      "Set max imbalance"
+    ).to.be.fulfilled;
+    await expectTX(adminManager.renounceAdmin(), "Renounce admin").to.be
+      .fulfilled;
+    await imbalanceReserves(new u64(9_000_000));
+
+    // nobody could unpause the swap anymore
+    const { state } = await loadSwap();
+    await expectTX(
+      poolWrapper.tripCircuitBreaker(state),
+      "Trip after renouncing"
+    ).to.be.rejected;
+    expect((await loadSwap()).state.isPaused).to.be.false;
+    const pool = await pmWrapper.loadPool(poolWrapper.key);
+    expect(pool.circuitBreakerTripped).to.be.false;
+  });
+});
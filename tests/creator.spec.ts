@@ -0,0 +1,120 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { RECOMMENDED_FEES, StableSwap } from "@saberhq/stableswap-sdk";
+import { u64 } from "@saberhq/token-utils";
+import type { Keypair } from "@solana/web3.js";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+
+import { MIN_RAMP_DURATION } from "../src/constants";
+import type { PoolWrapper } from "../src/wrappers/pool";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { createPool, makePoolManagerSDK } from "./testutils";
+import { createFundedKeypair, setupManagerWithPool } from "./workspace";
+
+describe("Saber Pool Manager pool creator", () => {
+  const sdk = makePoolManagerSDK();
+  const { provider } = sdk;
+
+  let pmWrapper: PoolManagerWrapper;
+  let otherCreatorsPool: PoolWrapper;
+  let creator: Keypair;
+  let creatorPool: PoolWrapper;
+
+  beforeEach(async () => {
+    let minter: Keypair;
+    let adminManager: PoolManagerWrapper;
+    // fees may be set immediately, so only missing access rejects them
+    ({
+      minter,
+      pmWrapper,
+      adminManager,
+      poolWrapper: otherCreatorsPool,
+    } = await setupManagerWithPool(sdk, { minFeeNoticePeriod: 0 }));
+
+    // the payer of a permissionless import becomes the creator of the pool
+    creator = await createFundedKeypair(sdk, 10 * LAMPORTS_PER_SOL);
+    const { pool } = await createPool(
+      provider,
+      minter,
+      pmWrapper.withSigner(creator),
+      new u64(100)
+    );
+    creatorPool = (await adminManager.loadPoolWrapper(pool)).withSigner(
+      creator
+    );
+  });
+
+  const stopRampTs = () =>
+    Math.floor(Date.now() / 1_000) + MIN_RAMP_DURATION + 60;
+
+  it("Creator may ramp their pool within the permissionless bounds", async () => {
+    const pool = await pmWrapper.loadPool(creatorPool.key);
+    expect(pool.creator).eqAddress(creator.publicKey);
+    expect(pool.permissionlessImport).to.be.true;
+
+    // the default permissionless bounds are 10 to 200
+    await expectTX(
+      creatorPool.creatorRampA(new u64(201), stopRampTs()),
+      "Ramp above the bounds"
+    ).to.be.rejected;
+    await expectTX(
+      creatorPool.creatorRampA(new u64(9), stopRampTs()),
+      "Ramp below the bounds"
+    ).to.be.rejected;
+
+    await expectTX(
+      creatorPool.creatorRampA(new u64(200), stopRampTs()),
+      "Ramp as creator"
+    ).to.be.fulfilled;
+    let { state } = await StableSwap.load(
+      provider.connection,
+      creatorPool.data.swap
+    );
+    expect(state.targetAmpFactor.toString()).to.equal("200");
+
+    await expectTX(creatorPool.creatorStopRampA(), "Stop ramp as creator").to
+      .be.fulfilled;
+    ({ state } = await StableSwap.load(
+      provider.connection,
+      creatorPool.data.swap
+    ));
+    expect(state.targetAmpFactor.toString()).to.equal(
+      state.initialAmpFactor.toString()
+    );
+  });
+
+  it("Creator may not ramp another creator's pool", async () => {
+    const foreignPool = otherCreatorsPool.withSigner(creator);
+    await expectTX(
+      foreignPool.creatorRampA(new u64(200), stopRampTs()),
+      "Ramp another creator's pool"
+    ).to.be.rejected;
+    await expectTX(
+      foreignPool.creatorStopRampA(),
+      "Stop ramp of another creator's pool"
+    ).to.be.rejected;
+  });
+
+  it("Creator may not pause or change fees", async () => {
+    await expectTX(creatorPool.pauseSwap(), "Pause as creator").to.be
+      .rejected;
+    await expectTX(
+      creatorPool.setNewFees(RECOMMENDED_FEES),
+      "Set fees as creator"
+    ).to.be.rejected;
+    await expectTX(
+      creatorPool.scheduleNewFees(
+        RECOMMENDED_FEES,
+        Math.floor(Date.now() / 1_000) + 60
+      ),
+      "Schedule fees as creator"
+    ).to.be.rejected;
+    // nor use the unrestricted ramp
+    await expectTX(
+      creatorPool.rampA(new u64(1_000), stopRampTs()),
+      "Ramp as creator without bounds"
+    ).to.be.rejected;
+  });
+});
@@ -0,0 +1,125 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { StableSwap } from "@saberhq/stableswap-sdk";
+import { u64 } from "@saberhq/token-utils";
+import type { Keypair } from "@solana/web3.js";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+
+import type { PoolWrapper } from "../src/wrappers/pool";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { createPool, makePoolManagerSDK } from "./testutils";
+import { createFundedKeypair, setupManagerWithPool } from "./workspace";
+
+/**
+ * Pyth-style price account trading at $0.90, loaded from
+ * `tests/fixtures/depegged-price.json`.
+ */
+const DEPEGGED_PRICE = new PublicKey(
+  "JDy44eRQGYYEH41gVLm9hhLMD7fr1kaANpLvkxjswkNd"
+);
+
+/**
+ * The fixture price is never stale.
+ */
+const MAX_STALENESS_SLOTS = new u64("18446744073709551615");
+
+describe("Saber Pool Manager depeg", () => {
+  const sdk = makePoolManagerSDK();
+  const { provider } = sdk;
+
+  let minter: Keypair;
+  let pmWrapper: PoolManagerWrapper;
+  let adminManager: PoolManagerWrapper;
+  let poolWrapper: PoolWrapper;
+  let mintA: PublicKey;
+  let mintB: PublicKey;
+
+  beforeEach(async () => {
+    ({ minter, pmWrapper, adminManager, poolWrapper, mintA, mintB } =
+      await setupManagerWithPool(sdk));
+  });
+
+  async function createMintOracle(mint: PublicKey, maxDeviationBps: number) {
+    const { mintOracle, tx } = await adminManager.createMintOracle({
+      mint,
+      priceAccount: DEPEGGED_PRICE,
+      pegPrice: new u64(100_000_000),
+      pegExpo: -8,
+      maxDeviationBps,
+      maxStalenessSlots: MAX_STALENESS_SLOTS,
+    });
+    await expectTX(tx, "Create mint oracle").to.be.fulfilled;
+    return mintOracle;
+  }
+
+  async function isPaused() {
+    const { state } = await StableSwap.load(
+      provider.connection,
+      poolWrapper.data.swap
+    );
+    return state.isPaused;
+  }
+
+  it("Price within the band does not pause", async () => {
+    // the price is 10% off the peg
+    await createMintOracle(mintA, 2_000);
+    await expectTX(
+      await pmWrapper.checkDepeg(mintA, [poolWrapper]),
+      "Check depeg within band"
+    ).to.be.rejected;
+    expect(await isPaused()).to.be.false;
+  });
+
+  it("Depegged price pauses swaps holding the mint", async () => {
+    const mintOracle = await createMintOracle(mintA, 500);
+    const oracle = await sdk.programs.Pools.account.mintOracle.fetch(
+      mintOracle
+    );
+    expect(oracle.mint).eqAddress(mintA);
+    expect(oracle.priceAccount).eqAddress(DEPEGGED_PRICE);
+
+    // anyone may check the price
+    const outsider = await createFundedKeypair(sdk);
+    await expectTX(
+      await pmWrapper.withSigner(outsider).checkDepeg(mintA, [poolWrapper]),
+      "Check depeg"
+    ).to.be.fulfilled;
+
+    expect(await isPaused()).to.be.true;
+    const pool = await pmWrapper.loadPool(poolWrapper.key);
+    expect(pool.circuitBreakerTripped).to.be.true;
+  });
+
+  it("Depeg may only pause swaps holding the mint", async () => {
+    await createMintOracle(mintB, 500);
+    const { mintA: otherMintA, mintB: otherMintB } = await createPool(
+      provider,
+      minter,
+      pmWrapper,
+      new u64(100)
+    );
+    const otherPool = await adminManager.loadPoolWrapperFromMints(
+      otherMintA,
+      otherMintB
+    );
+    await expectTX(
+      await pmWrapper.checkDepeg(mintB, [otherPool]),
+      "Check depeg of an unrelated pool"
+    ).to.be.rejected;
+  });
+
+  it("Depeg may not pause swaps once the admin is renounced", async () => {
+    await createMintOracle(mintA, 500);
+    await expectTX(adminManager.renounceAdmin(), "Renounce admin").to.be
+      .fulfilled;
+
+    // nobody could unpause the swap anymore
+    await expectTX(
+      await pmWrapper.checkDepeg(mintA, [poolWrapper]),
+      "Check depeg after renouncing"
+    ).to.be.rejected;
+    expect(await isPaused()).to.be.false;
+  });
+});
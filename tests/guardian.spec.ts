@@ -0,0 +1,98 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { RECOMMENDED_FEES, StableSwap } from "@saberhq/stableswap-sdk";
+import type { Keypair } from "@solana/web3.js";
+import { expect } from "chai";
+
+import type { PoolWrapper } from "../src/wrappers/pool";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { makePoolManagerSDK } from "./testutils";
+import { createFundedKeypair, setupManagerWithPool } from "./workspace";
+
+describe("Saber Pool Manager guardian", () => {
+  const sdk = makePoolManagerSDK();
+  const { provider } = sdk;
+
+  let pmWrapper: PoolManagerWrapper;
+  let poolWrapper: PoolWrapper;
+  let guardian: Keypair;
+  let guardianPool: PoolWrapper;
+
+  beforeEach(async () => {
+    // fees may be set immediately, so only missing access rejects them
+    ({ pmWrapper, poolWrapper } = await setupManagerWithPool(sdk, {
+      minFeeNoticePeriod: 0,
+    }));
+    guardian = await createFundedKeypair(sdk);
+    await expectTX(
+      poolWrapper.setGuardian(guardian.publicKey),
+      "Set guardian"
+    ).to.be.fulfilled;
+    guardianPool = poolWrapper.withSigner(guardian);
+  });
+
+  async function isPaused() {
+    const { state } = await StableSwap.load(
+      provider.connection,
+      poolWrapper.data.swap
+    );
+    return state.isPaused;
+  }
+
+  it("Guardian may pause but not unpause or change fees", async () => {
+    expect((await pmWrapper.reloadData()).guardian).eqAddress(
+      guardian.publicKey
+    );
+
+    await expectTX(guardianPool.pauseSwap(), "Pause as guardian").to.be
+      .fulfilled;
+    expect(await isPaused()).to.be.true;
+
+    await expectTX(guardianPool.unpauseSwap(), "Unpause as guardian").to.be
+      .rejected;
+    await expectTX(
+      guardianPool.setNewFees(RECOMMENDED_FEES),
+      "Set fees as guardian"
+    ).to.be.rejected;
+    await expectTX(
+      guardianPool.scheduleNewFees(
+        RECOMMENDED_FEES,
+        Math.floor(Date.now() / 1_000) + 60
+      ),
+      "Schedule fees as guardian"
+    ).to.be.rejected;
+    expect(await isPaused()).to.be.true;
+
+    await expectTX(poolWrapper.unpauseSwap(), "Unpause as admin").to.be
+      .fulfilled;
+    expect(await isPaused()).to.be.false;
+  });
+
+  it("Only the admin may set the guardian", async () => {
+    const outsider = await createFundedKeypair(sdk);
+    await expectTX(
+      guardianPool.setGuardian(outsider.publicKey),
+      "Set guardian as guardian"
+    ).to.be.rejected;
+    await expectTX(
+      poolWrapper.withSigner(outsider).setGuardian(outsider.publicKey),
+      "Set guardian as outsider"
+    ).to.be.rejected;
+    expect((await pmWrapper.reloadData()).guardian).eqAddress(
+      guardian.publicKey
+    );
+
+    // the replaced guardian may no longer pause
+    await expectTX(
+      poolWrapper.setGuardian(outsider.publicKey),
+      "Replace guardian"
+    ).to.be.fulfilled;
+    await expectTX(guardianPool.pauseSwap(), "Pause as former guardian").to.be
+      .rejected;
+    await expectTX(
+      poolWrapper.withSigner(outsider).pauseSwap(),
+      "Pause as new guardian"
+    ).to.be.fulfilled;
+  });
+});
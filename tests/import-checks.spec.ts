@@ -0,0 +1,79 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { DEFAULT_TOKEN_DECIMALS } from "@saberhq/stableswap-sdk";
+import { SPLToken, TOKEN_PROGRAM_ID, u64 } from "@saberhq/token-utils";
+import type { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+
+import { IMPORT_CHECKS } from "../src/constants";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { createPool, makePoolManagerSDK } from "./testutils";
+import { createFundedKeypair, setupManagerWithPool } from "./workspace";
+
+describe("Saber Pool Manager import checks", () => {
+  const sdk = makePoolManagerSDK();
+  const { provider } = sdk;
+
+  let minter: Keypair;
+  let pmWrapper: PoolManagerWrapper;
+  let adminManager: PoolManagerWrapper;
+
+  beforeEach(async () => {
+    ({ minter, pmWrapper, adminManager } = await setupManagerWithPool(sdk));
+  });
+
+  /**
+   * Creates a mint which the minter may freeze.
+   */
+  async function createFreezableMint(): Promise<PublicKey> {
+    const payer = await createFundedKeypair(sdk);
+    const token = await SPLToken.createMint(
+      provider.connection,
+      payer,
+      minter.publicKey,
+      minter.publicKey,
+      DEFAULT_TOKEN_DECIMALS,
+      TOKEN_PROGRAM_ID
+    );
+    return token.publicKey;
+  }
+
+  it("Enabled import checks apply to permissionless imports", async () => {
+    await expectTX(
+      adminManager.setImportChecks(IMPORT_CHECKS.NO_MINT_FREEZE_AUTHORITY),
+      "Set import checks"
+    ).to.be.fulfilled;
+    const manager = await pmWrapper.reloadData();
+    expect(manager.importChecks).to.equal(
+      IMPORT_CHECKS.NO_MINT_FREEZE_AUTHORITY
+    );
+
+    const freezableMint = await createFreezableMint();
+    await expect(
+      createPool(provider, minter, pmWrapper, new u64(100), freezableMint)
+    ).to.be.rejected;
+    // mints without a freeze authority pass
+    await expect(createPool(provider, minter, pmWrapper, new u64(100))).to.be
+      .fulfilled;
+
+    await expectTX(adminManager.setImportChecks(0), "Clear import checks").to
+      .be.fulfilled;
+    await expect(
+      createPool(provider, minter, pmWrapper, new u64(100), freezableMint)
+    ).to.be.fulfilled;
+  });
+
+  it("Only the admin may set known import checks", async () => {
+    // the provider wallet is not the admin
+    await expectTX(
+      pmWrapper.setImportChecks(IMPORT_CHECKS.ALL),
+      "Set import checks as outsider"
+    ).to.be.rejected;
+    await expectTX(
+      adminManager.setImportChecks(IMPORT_CHECKS.ALL + 1),
+      "Set unknown import checks"
+    ).to.be.rejected;
+    expect((await pmWrapper.reloadData()).importChecks).to.equal(0);
+  });
+});
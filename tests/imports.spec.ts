@@ -0,0 +1,111 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { u64 } from "@saberhq/token-utils";
+import type { Keypair } from "@solana/web3.js";
+import { LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { expect } from "chai";
+
+import type { PoolWrapper } from "../src/wrappers/pool";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { createPool, makePoolManagerSDK } from "./testutils";
+import {
+  createFundedKeypair,
+  deploySwap,
+  setupManagerWithPool,
+} from "./workspace";
+
+describe("Saber Pool Manager import kill switches", () => {
+  const sdk = makePoolManagerSDK();
+  const { provider } = sdk;
+
+  let minter: Keypair;
+  let pmWrapper: PoolManagerWrapper;
+  let adminManager: PoolManagerWrapper;
+  let poolWrapper: PoolWrapper;
+
+  beforeEach(async () => {
+    ({ minter, pmWrapper, adminManager, poolWrapper } =
+      await setupManagerWithPool(sdk));
+  });
+
+  const importPermissionless = () =>
+    createPool(provider, minter, pmWrapper, new u64(100));
+
+  /**
+   * Imports a new swap administered by the operator, as the operator.
+   */
+  async function importAsOperator(operator: Keypair) {
+    const { swap } = await deploySwap(provider, minter, {
+      admin: operator.publicKey,
+      ampFactor: new u64(1_000),
+    });
+    const { tx } = await pmWrapper
+      .withSigner(operator)
+      .importPoolAsOperator(swap.config.swapAccount, operator.publicKey);
+    return tx;
+  }
+
+  it("Admin may disable and re-enable each kind of import", async () => {
+    const operator = await createFundedKeypair(sdk, 10 * LAMPORTS_PER_SOL);
+    await expectTX(
+      poolWrapper.setOperator(operator.publicKey, undefined, true),
+      "Set operator"
+    ).to.be.fulfilled;
+
+    await expectTX(
+      adminManager.setImportsEnabled(false, true),
+      "Disable permissionless imports"
+    ).to.be.fulfilled;
+    let manager = await pmWrapper.reloadData();
+    expect(manager.permissionlessImportsEnabled).to.be.false;
+    expect(manager.operatorImportsEnabled).to.be.true;
+    await expect(importPermissionless()).to.be.rejected;
+    await expectTX(await importAsOperator(operator), "Import as operator").to
+      .be.fulfilled;
+
+    await expectTX(
+      adminManager.setImportsEnabled(true, false),
+      "Disable operator imports"
+    ).to.be.fulfilled;
+    manager = await pmWrapper.reloadData();
+    expect(manager.permissionlessImportsEnabled).to.be.true;
+    expect(manager.operatorImportsEnabled).to.be.false;
+    await expectTX(
+      await importAsOperator(operator),
+      "Import as operator while disabled"
+    ).to.be.rejected;
+    await expect(importPermissionless()).to.be.fulfilled;
+  });
+
+  it("Guardian may only disable imports", async () => {
+    const guardian = await createFundedKeypair(sdk);
+    await expectTX(
+      poolWrapper.setGuardian(guardian.publicKey),
+      "Set guardian"
+    ).to.be.fulfilled;
+    const guardianManager = pmWrapper.withSigner(guardian);
+
+    await expectTX(
+      guardianManager.setImportsEnabled(false, false),
+      "Disable imports as guardian"
+    ).to.be.fulfilled;
+    await expectTX(
+      guardianManager.setImportsEnabled(true, false),
+      "Enable imports as guardian"
+    ).to.be.rejected;
+    const manager = await pmWrapper.reloadData();
+    expect(manager.permissionlessImportsEnabled).to.be.false;
+    expect(manager.operatorImportsEnabled).to.be.false;
+
+    // the provider wallet is neither the admin nor the guardian
+    await expectTX(
+      pmWrapper.setImportsEnabled(false, false),
+      "Disable imports as outsider"
+    ).to.be.rejected;
+    await expectTX(
+      adminManager.setImportsEnabled(true, true),
+      "Enable imports as admin"
+    ).to.be.fulfilled;
+  });
+});
@@ -0,0 +1,81 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { RECOMMENDED_FEES } from "@saberhq/stableswap-sdk";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+
+import { PARAMS } from "../src/constants";
+import type { PoolWrapper } from "../src/wrappers/pool";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { makePoolManagerSDK } from "./testutils";
+import { setupManagerWithPool } from "./workspace";
+
+describe("Saber Pool Manager locks", () => {
+  const sdk = makePoolManagerSDK();
+
+  let pmWrapper: PoolManagerWrapper;
+  let adminManager: PoolManagerWrapper;
+  let poolWrapper: PoolWrapper;
+
+  beforeEach(async () => {
+    ({ pmWrapper, adminManager, poolWrapper } = await setupManagerWithPool(
+      sdk
+    ));
+  });
+
+  it("Locked params may not be changed", async () => {
+    await expectTX(
+      adminManager.lockParams(PARAMS.BENEFICIARY | PARAMS.POOL_FEES),
+      "Lock beneficiary and pool fees"
+    ).to.be.fulfilled;
+    const manager = await pmWrapper.reloadData();
+    expect(manager.lockedParams).to.equal(
+      PARAMS.BENEFICIARY | PARAMS.POOL_FEES
+    );
+
+    await expectTX(
+      poolWrapper.setBeneficiary(Keypair.generate().publicKey, true),
+      "Set locked beneficiary"
+    ).to.be.rejected;
+
+    const effectiveTs =
+      Math.floor(Date.now() / 1_000) +
+      manager.minFeeNoticePeriod.toNumber() +
+      60;
+    await expectTX(
+      poolWrapper.scheduleNewFees(RECOMMENDED_FEES, effectiveTs),
+      "Schedule locked pool fees"
+    ).to.be.rejected;
+
+    // unlocked params may still be changed
+    await expectTX(
+      poolWrapper.setOperator(Keypair.generate().publicKey),
+      "Set operator"
+    ).to.be.fulfilled;
+  });
+
+  it("Unknown params may not be locked", async () => {
+    await expectTX(
+      adminManager.lockParams(PARAMS.ALL + 1),
+      "Lock unknown params"
+    ).to.be.rejected;
+  });
+
+  it("Renounce admin", async () => {
+    await expectTX(adminManager.renounceAdmin(), "Renounce admin").to.be
+      .fulfilled;
+    const manager = await pmWrapper.reloadData();
+    expect(manager.admin).eqAddress(PublicKey.default);
+    expect(manager.pendingAdmin).eqAddress(PublicKey.default);
+
+    // the former admin, who was also the operator, has no powers left
+    await expectTX(adminManager.lockParams(PARAMS.ALL), "Lock params").to.be
+      .rejected;
+    await expectTX(poolWrapper.pauseSwap(), "Pause swap").to.be.rejected;
+    await expectTX(
+      poolWrapper.setBeneficiary(Keypair.generate().publicKey, true),
+      "Set beneficiary"
+    ).to.be.rejected;
+  });
+});
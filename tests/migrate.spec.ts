@@ -0,0 +1,89 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+
+import { NO_EXPIRY } from "../src/constants";
+import { comparePubkeys } from "../src/utils/comparePubkeys";
+import { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { makePoolManagerSDK } from "./testutils";
+
+/**
+ * PoolManager with the layout of the first release, administered by the
+ * test wallet. Loaded from `tests/fixtures/baseline-pool-manager.json`.
+ */
+const BASELINE_POOL_MANAGER = new PublicKey(
+  "CRyEyZoeZKLG8W5WJJKqtrpzhb8ihfP9GqTkKc9LgZ6X"
+);
+
+/**
+ * Pool of {@link BASELINE_POOL_MANAGER} with the layout of the first release.
+ * Loaded from `tests/fixtures/baseline-pool.json`.
+ */
+const BASELINE_POOL = new PublicKey(
+  "4a6fGpZLtbV9vnKLuWB76GcJ1Rz798dxp65pGgpaKS4b"
+);
+
+describe("Saber Pool Manager migration", () => {
+  const sdk = makePoolManagerSDK();
+  const { provider } = sdk;
+  const pmWrapper = new PoolManagerWrapper(sdk, BASELINE_POOL_MANAGER);
+
+  it("Un-migrated manager is rejected", async () => {
+    const info = await provider.connection.getAccountInfo(
+      BASELINE_POOL_MANAGER
+    );
+    expect(info?.data.length).to.equal(8 + 249);
+
+    await expectTX(
+      pmWrapper.setMaxImbalanceBps(100),
+      "Set max imbalance before migrating"
+    ).to.be.rejected;
+  });
+
+  it("Migrate a baseline manager", async () => {
+    await expectTX(pmWrapper.migrate(), "Migrate pool manager").to.be
+      .fulfilled;
+
+    const manager = await pmWrapper.reloadData();
+    expect(manager.version).to.equal(1);
+    expect(manager.admin).eqAddress(provider.wallet.publicKey);
+    expect(manager.numPools.toString()).to.equal("1");
+    expect(manager.initialFees.tradeFeeNumerator.toString()).to.equal("4");
+    expect(manager.operatorValidUntil.toString()).to.equal(
+      NO_EXPIRY.toString()
+    );
+    expect(manager.minFeeNoticePeriod.toNumber()).to.equal(86_400);
+    expect(manager.swapAdminChangeDelay.toNumber()).to.equal(7 * 86_400);
+    expect(manager.feeCaps.maxTradeFeeBps).to.equal(100);
+    expect(manager.permissionlessImportsEnabled).to.be.true;
+
+    await expectTX(pmWrapper.migrate(), "Migrate pool manager twice").to.be
+      .rejected;
+
+    // the migrated manager may be used again
+    await expectTX(
+      pmWrapper.setMaxImbalanceBps(100),
+      "Set max imbalance after migrating"
+    ).to.be.fulfilled;
+    expect((await pmWrapper.reloadData()).maxImbalanceBps).to.equal(100);
+  });
+
+  it("Migrate a baseline pool", async () => {
+    await expectTX(pmWrapper.migratePool(BASELINE_POOL), "Migrate pool").to
+      .be.fulfilled;
+
+    const pool = await pmWrapper.loadPool(BASELINE_POOL);
+    expect(pool.version).to.equal(1);
+    expect(pool.manager).eqAddress(BASELINE_POOL_MANAGER);
+    expect(pool.tokenDecimals).to.equal(6);
+    expect(pool.swapTokensReversed).to.equal(
+      comparePubkeys(pool.mintA, pool.mintB) === 1
+    );
+    expect(pool.scheduledFeesEffectiveTs.toString()).to.equal("0");
+
+    await expectTX(pmWrapper.migratePool(BASELINE_POOL), "Migrate pool twice")
+      .to.be.rejected;
+  });
+});
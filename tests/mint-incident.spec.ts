@@ -0,0 +1,117 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { StableSwap } from "@saberhq/stableswap-sdk";
+import { u64 } from "@saberhq/token-utils";
+import type { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+
+import type { PoolWrapper } from "../src/wrappers/pool";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { createPool, makePoolManagerSDK } from "./testutils";
+import { createFundedKeypair, setupManagerWithPool } from "./workspace";
+
+describe("Saber Pool Manager mint incidents", () => {
+  const sdk = makePoolManagerSDK();
+  const { provider } = sdk;
+
+  let admin: Keypair;
+  let pmWrapper: PoolManagerWrapper;
+  let adminManager: PoolManagerWrapper;
+  let mint: PublicKey;
+  let poolWrapper: PoolWrapper;
+  let sharedPool: PoolWrapper;
+  let unrelatedPool: PoolWrapper;
+
+  beforeEach(async () => {
+    let minter: Keypair;
+    ({
+      admin,
+      minter,
+      pmWrapper,
+      adminManager,
+      poolWrapper,
+      mintA: mint,
+    } = await setupManagerWithPool(sdk));
+
+    const { pool: shared } = await createPool(
+      provider,
+      minter,
+      pmWrapper,
+      new u64(100),
+      mint
+    );
+    sharedPool = await adminManager.loadPoolWrapper(shared);
+    const { pool: unrelated } = await createPool(
+      provider,
+      minter,
+      pmWrapper,
+      new u64(100)
+    );
+    unrelatedPool = await adminManager.loadPoolWrapper(unrelated);
+  });
+
+  async function isPaused(pool: PoolWrapper) {
+    const { state } = await StableSwap.load(
+      provider.connection,
+      pool.data.swap
+    );
+    return state.isPaused;
+  }
+
+  it("Pause every pool holding a mint", async () => {
+    const { mintIncident, tx } = await adminManager.pauseByMint(mint, [
+      poolWrapper,
+      sharedPool,
+    ]);
+    await expectTX(tx, "Pause by mint").to.be.fulfilled;
+    expect(await isPaused(poolWrapper)).to.be.true;
+    expect(await isPaused(sharedPool)).to.be.true;
+    expect(await isPaused(unrelatedPool)).to.be.false;
+
+    const incident = await sdk.programs.Pools.account.mintIncident.fetch(
+      mintIncident
+    );
+    expect(incident.manager).eqAddress(adminManager.key);
+    expect(incident.index.toNumber()).to.equal(0);
+    expect(incident.mint).eqAddress(mint);
+    expect(incident.authority).eqAddress(admin.publicKey);
+    expect(incident.numSwapsPaused.toNumber()).to.equal(2);
+    const manager = await pmWrapper.reloadData();
+    expect(manager.numMintIncidents.toNumber()).to.equal(1);
+  });
+
+  it("Guardian may pause by mint", async () => {
+    const guardian = await createFundedKeypair(sdk);
+    await expectTX(
+      poolWrapper.setGuardian(guardian.publicKey),
+      "Set guardian"
+    ).to.be.fulfilled;
+
+    const { mintIncident, tx } = await pmWrapper
+      .withSigner(guardian)
+      .pauseByMint(mint, [poolWrapper, sharedPool]);
+    await expectTX(tx, "Pause by mint as guardian").to.be.fulfilled;
+    const incident = await sdk.programs.Pools.account.mintIncident.fetch(
+      mintIncident
+    );
+    expect(incident.authority).eqAddress(guardian.publicKey);
+  });
+
+  it("Only pools holding the mint may be paused by the admin or guardian", async () => {
+    // the provider wallet is not the admin
+    const { tx: outsiderTx } = await pmWrapper.pauseByMint(mint, [
+      poolWrapper,
+      sharedPool,
+    ]);
+    await expectTX(outsiderTx, "Pause by mint as outsider").to.be.rejected;
+
+    const { tx } = await adminManager.pauseByMint(mint, [
+      poolWrapper,
+      unrelatedPool,
+    ]);
+    await expectTX(tx, "Pause a pool without the mint").to.be.rejected;
+    expect(await isPaused(poolWrapper)).to.be.false;
+    expect(await isPaused(unrelatedPool)).to.be.false;
+  });
+});
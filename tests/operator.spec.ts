@@ -0,0 +1,134 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { u64 } from "@saberhq/token-utils";
+import type { Keypair } from "@solana/web3.js";
+import { LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
+import BN from "bn.js";
+import { expect } from "chai";
+
+import type { PoolWrapper } from "../src/wrappers/pool";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { makePoolManagerSDK } from "./testutils";
+import {
+  createFundedKeypair,
+  deploySwap,
+  setupManagerWithPool,
+} from "./workspace";
+
+describe("Saber Pool Manager operator", () => {
+  const sdk = makePoolManagerSDK();
+  const { provider } = sdk;
+
+  let minter: Keypair;
+  let pmWrapper: PoolManagerWrapper;
+  let poolWrapper: PoolWrapper;
+  let operator: Keypair;
+
+  beforeEach(async () => {
+    ({ minter, pmWrapper, poolWrapper } = await setupManagerWithPool(sdk));
+    operator = await createFundedKeypair(sdk, 10 * LAMPORTS_PER_SOL);
+  });
+
+  /**
+   * Imports a new swap administered by the operator, as the operator.
+   */
+  async function importAsOperator() {
+    const { swap } = await deploySwap(provider, minter, {
+      admin: operator.publicKey,
+      ampFactor: new u64(1_000),
+    });
+    const { tx } = await pmWrapper
+      .withSigner(operator)
+      .importPoolAsOperator(swap.config.swapAccount, operator.publicKey);
+    return tx;
+  }
+
+  it("Operator may act until the delegation expires", async () => {
+    const validUntil = Math.floor(Date.now() / 1_000) + 3_600;
+    await expectTX(
+      poolWrapper.setOperator(operator.publicKey, new BN(validUntil), true),
+      "Set operator"
+    ).to.be.fulfilled;
+    const manager = await pmWrapper.reloadData();
+    expect(manager.operator).eqAddress(operator.publicKey);
+    expect(manager.operatorValidUntil.toNumber()).to.equal(validUntil);
+
+    await expectTX(await importAsOperator(), "Import as operator").to.be
+      .fulfilled;
+  });
+
+  it("Expired operator may not act", async () => {
+    const validUntil = Math.floor(Date.now() / 1_000) - 1;
+    await expectTX(
+      poolWrapper.setOperator(operator.publicKey, new BN(validUntil), true),
+      "Set expired operator"
+    ).to.be.fulfilled;
+
+    await expectTX(await importAsOperator(), "Import as expired operator").to
+      .be.rejected;
+  });
+
+  it("New operator must accept", async () => {
+    const previous = (await pmWrapper.reloadData()).operator;
+    await expectTX(
+      poolWrapper.setOperator(operator.publicKey),
+      "Commit operator"
+    ).to.be.fulfilled;
+    let manager = await pmWrapper.reloadData();
+    expect(manager.pendingOperator).eqAddress(operator.publicKey);
+    expect(manager.operator).eqAddress(previous);
+
+    // only the committed operator may accept
+    const outsider = await createFundedKeypair(sdk);
+    await expectTX(
+      poolWrapper.withSigner(outsider).acceptOperator(outsider.publicKey),
+      "Accept operator as outsider"
+    ).to.be.rejected;
+
+    await expectTX(
+      poolWrapper.withSigner(operator).acceptOperator(operator.publicKey),
+      "Accept operator"
+    ).to.be.fulfilled;
+    manager = await pmWrapper.reloadData();
+    expect(manager.operator).eqAddress(operator.publicKey);
+    expect(manager.pendingOperator).eqAddress(PublicKey.default);
+  });
+
+  it("New beneficiary must accept unless forced", async () => {
+    const beneficiary = await createFundedKeypair(sdk);
+    const previous = (await pmWrapper.reloadData()).beneficiary;
+    await expectTX(
+      poolWrapper.setBeneficiary(beneficiary.publicKey),
+      "Commit beneficiary"
+    ).to.be.fulfilled;
+    let manager = await pmWrapper.reloadData();
+    expect(manager.pendingBeneficiary).eqAddress(beneficiary.publicKey);
+    expect(manager.beneficiary).eqAddress(previous);
+
+    // only the committed beneficiary may accept
+    const outsider = await createFundedKeypair(sdk);
+    await expectTX(
+      poolWrapper.withSigner(outsider).acceptBeneficiary(outsider.publicKey),
+      "Accept beneficiary as outsider"
+    ).to.be.rejected;
+
+    await expectTX(
+      poolWrapper
+        .withSigner(beneficiary)
+        .acceptBeneficiary(beneficiary.publicKey),
+      "Accept beneficiary"
+    ).to.be.fulfilled;
+    manager = await pmWrapper.reloadData();
+    expect(manager.beneficiary).eqAddress(beneficiary.publicKey);
+
+    // forcing skips the acceptance
+    await expectTX(
+      poolWrapper.setBeneficiary(outsider.publicKey, true),
+      "Force beneficiary"
+    ).to.be.fulfilled;
+    manager = await pmWrapper.reloadData();
+    expect(manager.beneficiary).eqAddress(outsider.publicKey);
+    expect(manager.pendingBeneficiary).eqAddress(PublicKey.default);
+  });
+});
@@ -0,0 +1,115 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { RECOMMENDED_FEES } from "@saberhq/stableswap-sdk";
+import { Percent, u64 } from "@saberhq/token-utils";
+import type { Keypair } from "@solana/web3.js";
+import { PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+
+import { MIN_RAMP_DURATION } from "../src/constants";
+import type { PoolWrapper } from "../src/wrappers/pool";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { createPool, makePoolManagerSDK } from "./testutils";
+import { createFundedKeypair, setupManagerWithPool } from "./workspace";
+
+describe("Saber Pool Manager pool admin", () => {
+  const sdk = makePoolManagerSDK();
+  const { provider } = sdk;
+
+  let pmWrapper: PoolManagerWrapper;
+  let poolWrapper: PoolWrapper;
+  let otherPool: PoolWrapper;
+  let poolAdmin: Keypair;
+  let delegatedPool: PoolWrapper;
+
+  beforeEach(async () => {
+    let minter: Keypair;
+    let adminManager: PoolManagerWrapper;
+    // fees may be set immediately, so only missing access rejects them
+    ({ minter, pmWrapper, adminManager, poolWrapper } =
+      await setupManagerWithPool(sdk, { minFeeNoticePeriod: 0 }));
+    const { pool } = await createPool(
+      provider,
+      minter,
+      pmWrapper,
+      new u64(100)
+    );
+    otherPool = await adminManager.loadPoolWrapper(pool);
+
+    poolAdmin = await createFundedKeypair(sdk);
+    await expectTX(
+      poolWrapper.setPoolAdmin(poolAdmin.publicKey),
+      "Set pool admin"
+    ).to.be.fulfilled;
+    delegatedPool = poolWrapper.withSigner(poolAdmin);
+  });
+
+  const stopRampTs = () =>
+    Math.floor(Date.now() / 1_000) + MIN_RAMP_DURATION + 60;
+
+  it("Pool admin may ramp, pause and set fees on their pool", async () => {
+    const pool = await pmWrapper.loadPool(poolWrapper.key);
+    expect(pool.poolAdmin).eqAddress(poolAdmin.publicKey);
+
+    await expectTX(delegatedPool.pauseSwap(), "Pause as pool admin").to.be
+      .fulfilled;
+    await expectTX(delegatedPool.unpauseSwap(), "Unpause as pool admin").to.be
+      .fulfilled;
+    await expectTX(
+      delegatedPool.setNewFees(RECOMMENDED_FEES),
+      "Set fees as pool admin"
+    ).to.be.fulfilled;
+    await expectTX(
+      delegatedPool.rampA(new u64(200), stopRampTs()),
+      "Ramp as pool admin"
+    ).to.be.fulfilled;
+    await expectTX(delegatedPool.stopRampA(), "Stop ramp as pool admin").to
+      .be.fulfilled;
+  });
+
+  it("Pool admin is limited to their own pool", async () => {
+    const foreignPool = otherPool.withSigner(poolAdmin);
+    await expectTX(foreignPool.pauseSwap(), "Pause another pool").to.be
+      .rejected;
+    await expectTX(
+      foreignPool.setNewFees(RECOMMENDED_FEES),
+      "Set fees on another pool"
+    ).to.be.rejected;
+    await expectTX(
+      foreignPool.rampA(new u64(200), stopRampTs()),
+      "Ramp another pool"
+    ).to.be.rejected;
+
+    // the pool admin may not delegate the pool further
+    await expectTX(
+      delegatedPool.setPoolAdmin(PublicKey.default),
+      "Set pool admin as pool admin"
+    ).to.be.rejected;
+  });
+
+  it("Pool admin stays within the manager's caps", async () => {
+    // the default caps allow a trade fee of at most 1%
+    await expectTX(
+      delegatedPool.setNewFees({
+        ...RECOMMENDED_FEES,
+        trade: new Percent(2, 100),
+      }),
+      "Set fees above the caps"
+    ).to.be.rejected;
+    // the default ramp policy allows at most a tenfold change
+    await expectTX(
+      delegatedPool.rampA(new u64(10_000), stopRampTs()),
+      "Ramp beyond the ramp policy"
+    ).to.be.rejected;
+  });
+
+  it("Removing the pool admin revokes access", async () => {
+    await expectTX(
+      poolWrapper.setPoolAdmin(PublicKey.default),
+      "Remove pool admin"
+    ).to.be.fulfilled;
+    await expectTX(delegatedPool.pauseSwap(), "Pause as former pool admin").to
+      .be.rejected;
+  });
+});
@@ -2,24 +2,20 @@
 
 import { assertTXSuccess, expectTX } from "@saberhq/chai-solana";
 import {
+  createAdminApplyNewAdminInstruction,
   DEFAULT_TOKEN_DECIMALS,
-  deployNewSwap,
   StableSwap,
-  SWAP_PROGRAM_ID,
 } from "@saberhq/stableswap-sdk";
-import {
-  createMint,
-  getATAAddresses,
-  SPLToken,
-  TOKEN_PROGRAM_ID,
-  u64,
-} from "@saberhq/token-utils";
+import { getATAAddresses, u64 } from "@saberhq/token-utils";
 import type { PublicKey } from "@solana/web3.js";
-import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+import { Keypair, LAMPORTS_PER_SOL, SystemProgram } from "@solana/web3.js";
 import { expect } from "chai";
 
+import { findSaberPool, getRoleGrantAddress } from "../src/pda";
+import { comparePubkeys } from "../src/utils/comparePubkeys";
 import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
-import { makePoolManagerSDK } from "./testutils";
+import { makePoolManagerSDK, sleep } from "./testutils";
+import { deploySwap } from "./workspace";
 
 describe("Saber Pool Manager with existing swap", () => {
   const sdk = makePoolManagerSDK();
@@ -48,51 +44,11 @@ describe("Saber Pool Manager with existing swap", () => {
   });
 
   beforeEach(async () => {
-    mintA = await createMint(
-      provider,
-      minter.publicKey,
-      DEFAULT_TOKEN_DECIMALS
-    );
-    mintB = await createMint(
-      provider,
-      minter.publicKey,
-      DEFAULT_TOKEN_DECIMALS
-    );
-
-    const initialAdmin = minter.publicKey;
-
-    const { swap } = await deployNewSwap({
-      provider,
-      swapProgramID: SWAP_PROGRAM_ID,
-
-      tokenAMint: mintA,
-      tokenBMint: mintB,
-      adminAccount: minter.publicKey,
-      ampFactor: new u64(1_000),
-
-      seedPoolAccounts: ({ tokenAAccount, tokenBAccount }) => ({
-        instructions: [
-          SPLToken.createMintToInstruction(
-            TOKEN_PROGRAM_ID,
-            mintA,
-            tokenAAccount,
-            initialAdmin,
-            [],
-            1_000_000
-          ),
-          SPLToken.createMintToInstruction(
-            TOKEN_PROGRAM_ID,
-            mintB,
-            tokenBAccount,
-            minter.publicKey,
-            [],
-            1_000_000
-          ),
-        ],
-        signers: [minter],
-      }),
-    });
-    stableSwap = swap;
+    ({
+      swap: stableSwap,
+      mintA,
+      mintB,
+    } = await deploySwap(provider, minter, { ampFactor: new u64(1_000) }));
   });
 
   it("Import existing pool by admin", async () => {
@@ -134,4 +90,148 @@ describe("Saber Pool Manager with existing swap", () => {
       accounts.mintB.address
     );
   });
+
+  it("Import existing pool with unsorted mints", async () => {
+    const { swap } = await deploySwap(provider, minter, {
+      ampFactor: new u64(1_000),
+      unsortedMints: true,
+    });
+    const { swapAccount } = swap.config;
+    const { tokenA, tokenB, poolTokenMint } = swap.state;
+    expect(comparePubkeys(tokenA.mint, tokenB.mint)).to.equal(1);
+
+    const { poolKey, tx: handOverTx } = await poolManager.commitPoolAdoption(
+      swapAccount
+    );
+    handOverTx.instructions.push(
+      createAdminApplyNewAdminInstruction({
+        config: swap.config,
+        state: swap.state,
+      })
+    );
+    handOverTx.addSigners(minter);
+    await expectTX(handOverTx, "Hand the swap over to its pool").to.be
+      .fulfilled;
+
+    // the pool address uses the sorted mints
+    const [pool, bump] = await findSaberPool(
+      poolManager.key,
+      tokenB.mint,
+      tokenA.mint
+    );
+    expect(pool).eqAddress(poolKey);
+    const { accounts: fees } = await getATAAddresses({
+      mints: { tokenA: tokenA.mint, tokenB: tokenB.mint },
+      owner: pool,
+    });
+    const sideA = {
+      mint: tokenA.mint,
+      reserve: tokenA.reserve,
+      fees: fees.tokenA.address,
+    };
+    const sideB = {
+      mint: tokenB.mint,
+      reserve: tokenB.reserve,
+      fees: fees.tokenB.address,
+    };
+    const importTx = (first: typeof sideA, second: typeof sideA) =>
+      sdk.newTx(
+        [
+          sdk.programs.Pools.instruction.importPoolAsOperator(bump, {
+            accounts: {
+              authority: minter.publicKey,
+              roleGrant: getRoleGrantAddress(
+                poolManager.key,
+                minter.publicKey
+              ),
+              importPool: {
+                poolManager: poolManager.key,
+                swap: swapAccount,
+                pool,
+                lpMint: poolTokenMint,
+                tokenAFees: first.fees,
+                tokenBFees: second.fees,
+                tokenAMint: first.mint,
+                tokenBMint: second.mint,
+                tokenAReserve: first.reserve,
+                tokenBReserve: second.reserve,
+                payer: provider.wallet.publicKey,
+                systemProgram: SystemProgram.programId,
+              },
+            },
+          }),
+        ],
+        [minter]
+      );
+
+    // the accounts must follow the sides of the swap, not the sorted mints
+    await expectTX(importTx(sideB, sideA), "Import with sorted accounts").to
+      .be.rejected;
+    await expectTX(importTx(sideA, sideB), "Import with unsorted mints").to.be
+      .fulfilled;
+
+    const data = await poolManager.loadPool(pool);
+    expect(data.swap).eqAddress(swapAccount);
+    expect(data.mintA).eqAddress(tokenA.mint);
+    expect(data.tokenAFees).eqAddress(sideA.fees);
+    expect(data.swapTokensReversed).to.be.true;
+  });
+
+  it("Adopt existing pool after committing the pool as admin", async () => {
+    // the swap must satisfy the permissionless import parameters
+    const { swap } = await deploySwap(provider, minter, {
+      ampFactor: new u64(100),
+      fees: await poolManager.loadInitialFees(),
+    });
+    const { swapAccount } = swap.config;
+
+    const { poolKey, tx: commitTx } = await poolManager.commitPoolAdoption(
+      swapAccount
+    );
+    commitTx.addSigners(minter);
+    await expectTX(commitTx, "Commit pool adoption").to.be.fulfilled;
+    const committed = await StableSwap.load(provider.connection, swapAccount);
+    expect(committed.state.futureAdminAccount).eqAddress(poolKey);
+
+    await sleep(1_000);
+
+    const { poolBump, tx } = await poolManager.adoptPool(
+      swapAccount,
+      minter.publicKey
+    );
+    tx.addSigners(minter);
+    await expectTX(tx, "Adopt pool").to.be.fulfilled;
+
+    const data = await poolManager.loadPool(poolKey);
+    expect(data.bump).to.equal(poolBump);
+    expect(data.swap).eqAddress(swapAccount);
+    expect(data.creator).eqAddress(minter.publicKey);
+    expect(data.permissionlessImport).to.be.true;
+    const adopted = await StableSwap.load(provider.connection, swapAccount);
+    expect(adopted.state.adminAccount).eqAddress(poolKey);
+  });
+
+  it("Pool may not be adopted without the swap admin", async () => {
+    const { swap } = await deploySwap(provider, minter, {
+      ampFactor: new u64(100),
+      fees: await poolManager.loadInitialFees(),
+    });
+    const { swapAccount } = swap.config;
+
+    // the pool has not been committed as the new admin yet
+    const { tx: uncommittedTx } = await poolManager.adoptPool(
+      swapAccount,
+      minter.publicKey
+    );
+    uncommittedTx.addSigners(minter);
+    await expectTX(uncommittedTx, "Adopt uncommitted pool").to.be.rejected;
+
+    const { tx: commitTx } = await poolManager.commitPoolAdoption(swapAccount);
+    commitTx.addSigners(minter);
+    await expectTX(commitTx, "Commit pool adoption").to.be.fulfilled;
+
+    // only the previous admin may apply the new admin
+    const { tx } = await poolManager.adoptPool(swapAccount);
+    await expectTX(tx, "Adopt pool as another account").to.be.rejected;
+  });
 });
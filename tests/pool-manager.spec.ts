@@ -14,6 +14,7 @@ import {
   createMintToInstruction,
   getATAAddresses,
   getTokenAccount,
+  Percent,
   u64,
 } from "@saberhq/token-utils";
 import { Keypair, LAMPORTS_PER_SOL, PublicKey } from "@solana/web3.js";
@@ -24,7 +25,8 @@ import { MIN_RAMP_DURATION } from "../src/constants";
 import { findSaberPool, findSaberPoolManager } from "../src/pda";
 import { comparePubkeys } from "../src/utils/comparePubkeys";
 import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
-import { createPool, makePoolManagerSDK } from "./testutils";
+import { createPool, makePoolManagerSDK, sleep } from "./testutils";
+import { setupManagerWithPool } from "./workspace";
 
 describe("Saber Pool Manager", () => {
   const sdk = makePoolManagerSDK();
@@ -182,54 +184,266 @@ describe("Saber Pool Manager", () => {
     const commitNewAdminTx = poolWrapper.commitNewAdmin(newAdmin.publicKey);
     await expectTX(commitNewAdminTx, "Commit new admin").to.be.fulfilled;
 
-    const applyNewAdminTx = poolWrapper.applyNewAdmin();
-    await expectTX(applyNewAdminTx, "Apply new admin").to.be.fulfilled;
+    const pool = await pmWrapper.loadPool(poolWrapper.key);
+    expect(pool.pendingSwapAdmin).eqAddress(newAdmin.publicKey);
+    expect(pool.pendingSwapAdminEffectiveTs.toNumber()).to.be.greaterThan(
+      Math.floor(Date.now() / 1_000)
+    );
+
+    // the new admin may only be applied after the change delay
+    const applyNewAdminTx = poolWrapper.applyNewAdmin(newAdmin.publicKey);
+    await expectTX(applyNewAdminTx, "Apply new admin").to.be.rejected;
+
+    expect(await getSwapAdmin()).to.equal(poolWrapper.key.toString());
+  });
+
+  it("Apply new admin after the change delay", async () => {
+    const swapAdminChangeDelay = 5;
+    const {
+      pmWrapper: manager,
+      adminManager,
+      poolWrapper,
+    } = await setupManagerWithPool(sdk, { swapAdminChangeDelay });
+    expect(
+      (await manager.reloadData()).swapAdminChangeDelay.toNumber()
+    ).to.equal(swapAdminChangeDelay);
+
+    const newAdmin = Keypair.generate();
+    await expectTX(
+      poolWrapper.commitNewAdmin(newAdmin.publicKey),
+      "Commit new admin"
+    ).to.be.fulfilled;
+    await expectTX(
+      poolWrapper.applyNewAdmin(newAdmin.publicKey),
+      "Apply new admin early"
+    ).to.be.rejected;
+
+    await sleep((swapAdminChangeDelay + 2) * 1_000);
+    await expectTX(
+      poolWrapper.applyNewAdmin(newAdmin.publicKey),
+      "Apply new admin"
+    ).to.be.fulfilled;
+    const { state } = await StableSwap.load(
+      provider.connection,
+      poolWrapper.data.swap
+    );
+    expect(state.adminAccount).eqAddress(newAdmin.publicKey);
 
-    expect(await getSwapAdmin()).to.equal(newAdmin.publicKey.toString());
+    // the delay may be raised, but not lowered once pools exist
+    await expectTX(
+      adminManager.setSwapAdminChangeDelay(0),
+      "Lower swap admin change delay"
+    ).to.be.rejected;
+    await expectTX(
+      adminManager.setSwapAdminChangeDelay(swapAdminChangeDelay * 2),
+      "Raise swap admin change delay"
+    ).to.be.fulfilled;
   });
 
-  it("Set new fees", async () => {
+  it("Set new fees requires notice", async () => {
     const poolWrapper = await pmWrapper
       .withSigner(admin)
       .loadPoolWrapperFromMints(mintA, mintB);
 
-    async function getFees() {
-      const swap = await StableSwap.load(
-        sdk.provider.connection,
-        poolWrapper.data.swap
-      );
-      return swap.state.fees;
-    }
-
+    // the default notice period is non-zero, so fees must be scheduled
     const setNewFeesTx = poolWrapper.setNewFees(RECOMMENDED_FEES);
-    await expectTX(setNewFeesTx, "Set new fees").to.be.fulfilled;
+    await expectTX(setNewFeesTx, "Set new fees").to.be.rejected;
+
+    const manager = await pmWrapper.reloadData();
+    const effectiveTs =
+      Math.floor(Date.now() / 1_000) +
+      manager.minFeeNoticePeriod.toNumber() +
+      60;
+    await expectTX(
+      poolWrapper.scheduleNewFees(RECOMMENDED_FEES, effectiveTs),
+      "Schedule new fees"
+    ).to.be.fulfilled;
 
-    const newFees = await getFees();
-    expect(newFees.adminTrade.numerator.toString()).to.equal(
-      RECOMMENDED_FEES.adminTrade.numerator.toString()
+    const pool = await pmWrapper.loadPool(poolWrapper.key);
+    expect(pool.scheduledFeesEffectiveTs.toNumber()).to.equal(effectiveTs);
+    expect(pool.scheduledFees.tradeFeeNumerator.toString()).to.equal(
+      RECOMMENDED_FEES.trade.numerator.toString()
     );
-    expect(newFees.adminWithdraw.numerator.toString()).to.equal(
-      RECOMMENDED_FEES.adminWithdraw.numerator.toString()
+  });
+
+  it("Lowering the fee notice period is delayed", async () => {
+    const adminManager = pmWrapper.withSigner(admin);
+    const initial = (await pmWrapper.reloadData()).minFeeNoticePeriod;
+
+    await expectTX(
+      adminManager.setMinFeeNoticePeriod(0),
+      "Lower fee notice period"
+    ).to.be.fulfilled;
+    let manager = await pmWrapper.reloadData();
+    expect(manager.minFeeNoticePeriod.toString()).to.equal(initial.toString());
+    expect(manager.pendingMinFeeNoticePeriod.toString()).to.equal("0");
+    expect(
+      manager.pendingMinFeeNoticePeriodEffectiveTs.toNumber()
+    ).to.be.greaterThan(0);
+
+    await expectTX(
+      adminManager.applyMinFeeNoticePeriod(),
+      "Apply fee notice period early"
+    ).to.be.rejected;
+
+    // raising the notice period is immediate and clears the pending change
+    const raised = initial.toNumber() * 2;
+    await expectTX(
+      adminManager.setMinFeeNoticePeriod(raised),
+      "Raise fee notice period"
+    ).to.be.fulfilled;
+    manager = await pmWrapper.reloadData();
+    expect(manager.minFeeNoticePeriod.toNumber()).to.equal(raised);
+    expect(manager.pendingMinFeeNoticePeriodEffectiveTs.toString()).to.equal(
+      "0"
     );
-    expect(newFees.trade.numerator.toString()).to.equal(
-      RECOMMENDED_FEES.trade.numerator.toString()
+  });
+
+  it("Fee caps bound scheduled fees and are delayed", async () => {
+    const adminManager = pmWrapper.withSigner(admin);
+    const poolWrapper = await adminManager.loadPoolWrapperFromMints(
+      mintA,
+      mintB
     );
-    expect(newFees.withdraw.numerator.toString()).to.equal(
-      RECOMMENDED_FEES.withdraw.numerator.toString()
+
+    const manager = await pmWrapper.reloadData();
+    const effectiveTs =
+      Math.floor(Date.now() / 1_000) +
+      manager.minFeeNoticePeriod.toNumber() +
+      60;
+    // the default caps allow a trade fee of at most 1%
+    const excessiveFees = { ...RECOMMENDED_FEES, trade: new Percent(2, 100) };
+    await expectTX(
+      poolWrapper.scheduleNewFees(excessiveFees, effectiveTs),
+      "Schedule fees above the caps"
+    ).to.be.rejected;
+
+    const newFeeCaps = {
+      maxTradeFeeBps: 500,
+      maxWithdrawFeeBps: 500,
+      maxAdminFeeShareBps: 5_000,
+    };
+    await expectTX(
+      adminManager.commitNewFeeCaps(newFeeCaps),
+      "Commit new fee caps"
+    ).to.be.fulfilled;
+    const updated = await pmWrapper.reloadData();
+    expect(updated.pendingFeeCaps.maxTradeFeeBps).to.equal(500);
+    expect(updated.pendingFeeCapsEffectiveTs.toNumber()).to.be.greaterThan(
+      Math.floor(Date.now() / 1_000)
+    );
+    expect(updated.feeCaps.maxTradeFeeBps).to.equal(
+      manager.feeCaps.maxTradeFeeBps
     );
 
-    expect(newFees.adminTrade.denominator.toString()).to.equal(
-      RECOMMENDED_FEES.adminTrade.denominator.toString()
+    // the new caps may only be applied after the change delay
+    await expectTX(adminManager.applyNewFeeCaps(), "Apply fee caps early").to
+      .be.rejected;
+    await expectTX(
+      poolWrapper.scheduleNewFees(excessiveFees, effectiveTs),
+      "Schedule fees above the current caps"
+    ).to.be.rejected;
+  });
+
+  it("Set new fees without a notice period", async () => {
+    const { pmWrapper: manager, poolWrapper } = await setupManagerWithPool(
+      sdk,
+      { minFeeNoticePeriod: 0 }
     );
-    expect(newFees.adminWithdraw.denominator.toString()).to.equal(
-      RECOMMENDED_FEES.adminWithdraw.denominator.toString()
+    // there were no pools yet, so the notice period was lowered immediately
+    const { minFeeNoticePeriod } = await manager.reloadData();
+    expect(minFeeNoticePeriod.toString()).to.equal("0");
+
+    await expectTX(poolWrapper.setNewFees(RECOMMENDED_FEES), "Set new fees").to
+      .be.fulfilled;
+    const { state } = await StableSwap.load(
+      provider.connection,
+      poolWrapper.data.swap
     );
-    expect(newFees.trade.denominator.toString()).to.equal(
-      RECOMMENDED_FEES.trade.denominator.toString()
+    expect(state.fees.trade.numerator.toString()).to.equal(
+      RECOMMENDED_FEES.trade.numerator.toString()
     );
-    expect(newFees.withdraw.denominator.toString()).to.equal(
-      RECOMMENDED_FEES.withdraw.denominator.toString()
+
+    // the default caps allow a trade fee of at most 1%
+    await expectTX(
+      poolWrapper.setNewFees({
+        ...RECOMMENDED_FEES,
+        trade: new Percent(2, 100),
+      }),
+      "Set fees above the caps"
+    ).to.be.rejected;
+  });
+
+  it("Apply scheduled fees after the notice period", async () => {
+    const minFeeNoticePeriod = 2;
+    const { pmWrapper: manager, poolWrapper } = await setupManagerWithPool(
+      sdk,
+      { minFeeNoticePeriod }
     );
+    const now = Math.floor(Date.now() / 1_000);
+
+    await expectTX(
+      poolWrapper.scheduleNewFees(RECOMMENDED_FEES, now),
+      "Schedule fees within the notice period"
+    ).to.be.rejected;
+
+    const effectiveTs = now + minFeeNoticePeriod + 3;
+    await expectTX(
+      poolWrapper.scheduleNewFees(RECOMMENDED_FEES, effectiveTs),
+      "Schedule new fees"
+    ).to.be.fulfilled;
+
+    // anyone may apply the fees, but only once they are effective
+    const outsiderPool = await manager.loadPoolWrapper(poolWrapper.key);
+    await expectTX(
+      outsiderPool.applyScheduledFees(),
+      "Apply scheduled fees early"
+    ).to.be.rejected;
+
+    await sleep((effectiveTs + 2) * 1_000 - Date.now());
+    await expectTX(outsiderPool.applyScheduledFees(), "Apply scheduled fees")
+      .to.be.fulfilled;
+
+    const { state } = await StableSwap.load(
+      provider.connection,
+      poolWrapper.data.swap
+    );
+    expect(state.fees.trade.numerator.toString()).to.equal(
+      RECOMMENDED_FEES.trade.numerator.toString()
+    );
+    const pool = await manager.loadPool(poolWrapper.key);
+    expect(pool.scheduledFeesEffectiveTs.toString()).to.equal("0");
+
+    // the scheduled fees may only be applied once
+    await expectTX(
+      outsiderPool.applyScheduledFees(),
+      "Apply scheduled fees again"
+    ).to.be.rejected;
+  });
+
+  it("Cancel scheduled fees", async () => {
+    const { pmWrapper: manager, poolWrapper } = await setupManagerWithPool(
+      sdk,
+      { minFeeNoticePeriod: 0 }
+    );
+
+    await expectTX(poolWrapper.cancelScheduledFees(), "Cancel without fees").to
+      .be.rejected;
+
+    const effectiveTs = Math.floor(Date.now() / 1_000) + 60;
+    await expectTX(
+      poolWrapper.scheduleNewFees(RECOMMENDED_FEES, effectiveTs),
+      "Schedule new fees"
+    ).to.be.fulfilled;
+    await expectTX(poolWrapper.cancelScheduledFees(), "Cancel scheduled fees")
+      .to.be.fulfilled;
+
+    const pool = await manager.loadPool(poolWrapper.key);
+    expect(pool.scheduledFeesEffectiveTs.toString()).to.equal("0");
+    await expectTX(
+      poolWrapper.applyScheduledFees(),
+      "Apply cancelled fees"
+    ).to.be.rejected;
   });
 
   it("Send fees to beneficiary", async () => {
@@ -0,0 +1,133 @@
+/// <reference types="mocha" />
+
+import { assertTXThrows, expectTX } from "@saberhq/chai-solana";
+import { u64 } from "@saberhq/token-utils";
+import BN from "bn.js";
+import { expect } from "chai";
+
+import { MIN_RAMP_DURATION } from "../src/constants";
+import { PoolsErrors } from "../src/idls/pools";
+import type { RampPolicy } from "../src/types";
+import type { PoolWrapper } from "../src/wrappers/pool";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { makePoolManagerSDK } from "./testutils";
+import { setupManagerWithPool } from "./workspace";
+
+describe("Saber Pool Manager ramps", () => {
+  const sdk = makePoolManagerSDK();
+
+  // stricter than the limits of stable-swap itself
+  const rampPolicy: RampPolicy = {
+    maxChangeFactor: new u64(2),
+    minDuration: new BN(2 * MIN_RAMP_DURATION),
+    minCooldown: new BN(3 * MIN_RAMP_DURATION),
+  };
+
+  let pmWrapper: PoolManagerWrapper;
+  let adminManager: PoolManagerWrapper;
+  let poolWrapper: PoolWrapper;
+
+  beforeEach(async () => {
+    ({ pmWrapper, adminManager, poolWrapper } = await setupManagerWithPool(
+      sdk,
+      { rampPolicy }
+    ));
+  });
+
+  const now = () => Math.floor(Date.now() / 1_000);
+
+  it("Ramps must follow the ramp policy", async () => {
+    const manager = await pmWrapper.reloadData();
+    expect(manager.rampPolicy.maxChangeFactor.toNumber()).to.equal(2);
+
+    await expectTX(
+      poolWrapper.rampA(new u64(300), now() + 2 * MIN_RAMP_DURATION + 60),
+      "Ramp beyond the max change factor"
+    ).to.be.rejected;
+    await expectTX(
+      poolWrapper.rampA(new u64(200), now() + MIN_RAMP_DURATION + 60),
+      "Ramp shorter than the min duration"
+    ).to.be.rejected;
+
+    await expectTX(
+      poolWrapper.rampA(new u64(200), now() + 2 * MIN_RAMP_DURATION + 60),
+      "Ramp within the ramp policy"
+    ).to.be.fulfilled;
+    const pool = await pmWrapper.loadPool(poolWrapper.key);
+    expect(pool.lastRampTs.toNumber()).to.be.greaterThan(0);
+
+    // stopping a ramp does not restart the cooldown
+    await expectTX(poolWrapper.stopRampA(), "Stop ramp").to.be.fulfilled;
+    await expectTX(
+      poolWrapper.rampA(new u64(150), now() + 2 * MIN_RAMP_DURATION + 60),
+      "Ramp during the cooldown"
+    ).to.be.rejected;
+  });
+
+  it("Ramps are checked against stable-swap before the CPI", async () => {
+    const stopRampTs = now() + 2 * MIN_RAMP_DURATION + 60;
+    await assertTXThrows(
+      poolWrapper.rampA(new u64(200), now() + 60),
+      PoolsErrors.InsufficientRampTime
+    );
+    await assertTXThrows(
+      poolWrapper.rampA(new u64(0), stopRampTs),
+      PoolsErrors.TargetAmpOutOfRange
+    );
+    await assertTXThrows(
+      poolWrapper.rampA(new u64(2_000_000), stopRampTs),
+      PoolsErrors.TargetAmpOutOfRange
+    );
+    // the ramp policy is only checked once stable-swap would accept the ramp
+    await assertTXThrows(
+      poolWrapper.rampA(new u64(1_100), stopRampTs),
+      PoolsErrors.AmpChangeExceedsSwapLimit
+    );
+
+    await expectTX(poolWrapper.rampA(new u64(200), stopRampTs), "Ramp").to.be
+      .fulfilled;
+    await assertTXThrows(
+      poolWrapper.rampA(new u64(150), stopRampTs),
+      PoolsErrors.RampInProgress
+    );
+    await expectTX(poolWrapper.stopRampA(), "Stop ramp").to.be.fulfilled;
+    await assertTXThrows(
+      poolWrapper.rampA(new u64(150), stopRampTs),
+      PoolsErrors.RampLocked
+    );
+  });
+
+  it("Ramp policy changes are delayed once pools exist", async () => {
+    await expectTX(
+      pmWrapper.commitNewRampPolicy({
+        ...rampPolicy,
+        maxChangeFactor: new u64(10),
+      }),
+      "Commit ramp policy as outsider"
+    ).to.be.rejected;
+
+    await expectTX(
+      adminManager.commitNewRampPolicy({
+        ...rampPolicy,
+        maxChangeFactor: new u64(10),
+      }),
+      "Commit ramp policy"
+    ).to.be.fulfilled;
+    const manager = await pmWrapper.reloadData();
+    expect(manager.rampPolicy.maxChangeFactor.toNumber()).to.equal(2);
+    expect(manager.pendingRampPolicy.maxChangeFactor.toNumber()).to.equal(10);
+    expect(manager.pendingRampPolicyEffectiveTs.toNumber()).to.be.greaterThan(
+      now()
+    );
+
+    await expectTX(adminManager.applyNewRampPolicy(), "Apply ramp policy early")
+      .to.be.rejected;
+    await expectTX(
+      adminManager.commitNewRampPolicy({
+        ...rampPolicy,
+        maxChangeFactor: new u64(0),
+      }),
+      "Commit invalid ramp policy"
+    ).to.be.rejected;
+  });
+});
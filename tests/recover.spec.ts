@@ -0,0 +1,152 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { StableSwap } from "@saberhq/stableswap-sdk";
+import {
+  createTokenAccount,
+  getATAAddress,
+  getTokenAccount,
+  SPLToken,
+  TOKEN_PROGRAM_ID,
+} from "@saberhq/token-utils";
+import type { Keypair, PublicKey } from "@solana/web3.js";
+import { LAMPORTS_PER_SOL, SystemProgram } from "@solana/web3.js";
+import { expect } from "chai";
+
+import { ROLES } from "../src/constants";
+import type { PoolWrapper } from "../src/wrappers/pool";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { makePoolManagerSDK } from "./testutils";
+import { createFundedKeypair, setupManagerWithPool } from "./workspace";
+
+describe("Saber Pool Manager recovery", () => {
+  const sdk = makePoolManagerSDK();
+  const { provider } = sdk;
+
+  let minter: Keypair;
+  let adminManager: PoolManagerWrapper;
+  let poolWrapper: PoolWrapper;
+  let mintA: PublicKey;
+  let beneficiary: Keypair;
+
+  beforeEach(async () => {
+    ({ minter, adminManager, poolWrapper, mintA } = await setupManagerWithPool(
+      sdk
+    ));
+    // the beneficiary does not pay for any transaction
+    beneficiary = await createFundedKeypair(sdk);
+    await expectTX(
+      poolWrapper.setBeneficiary(beneficiary.publicKey, true),
+      "Set beneficiary"
+    ).to.be.fulfilled;
+  });
+
+  /**
+   * Creates a token account owned by the pool which is not one of its
+   * fee accounts, and sends tokens to it.
+   */
+  async function createStrayTokenAccount(amount: number) {
+    const { key, tx } = await createTokenAccount({
+      provider,
+      mint: mintA,
+      owner: poolWrapper.key,
+    });
+    tx.instructions.push(
+      SPLToken.createMintToInstruction(
+        TOKEN_PROGRAM_ID,
+        mintA,
+        key,
+        minter.publicKey,
+        [],
+        amount
+      )
+    );
+    tx.addSigners(minter);
+    await expectTX(tx, "Send stray tokens to the pool").to.be.fulfilled;
+    return key;
+  }
+
+  it("Recover stray tokens to the beneficiary", async () => {
+    const stray = await createStrayTokenAccount(1_000);
+    await expectTX(
+      await poolWrapper.recoverTokens(stray, mintA),
+      "Recover tokens"
+    ).to.be.fulfilled;
+
+    const swept = await getTokenAccount(provider, stray);
+    expect(swept.amount.toNumber()).to.equal(0);
+    const beneficiaryAccount = await getATAAddress({
+      mint: mintA,
+      owner: beneficiary.publicKey,
+    });
+    const recovered = await getTokenAccount(provider, beneficiaryAccount);
+    expect(recovered.amount.toNumber()).to.equal(1_000);
+
+    // a sweeper may recover tokens too
+    const sweeper = await createFundedKeypair(sdk);
+    const { tx } = adminManager.createRoleGrant(
+      sweeper.publicKey,
+      ROLES.SWEEPER
+    );
+    await expectTX(tx, "Grant sweeper role").to.be.fulfilled;
+    const secondStray = await createStrayTokenAccount(500);
+    await expectTX(
+      await poolWrapper.withSigner(sweeper).recoverTokens(secondStray, mintA),
+      "Recover tokens as sweeper"
+    ).to.be.fulfilled;
+  });
+
+  it("Fee accounts and reserves may not be recovered", async () => {
+    const { state } = await StableSwap.load(
+      provider.connection,
+      poolWrapper.data.swap
+    );
+    await expectTX(
+      await poolWrapper.recoverTokens(state.tokenA.adminFeeAccount, mintA),
+      "Recover fee account"
+    ).to.be.rejected;
+    await expectTX(
+      await poolWrapper.recoverTokens(state.tokenA.reserve, state.tokenA.mint),
+      "Recover reserve"
+    ).to.be.rejected;
+
+    const stray = await createStrayTokenAccount(1_000);
+    const outsider = await createFundedKeypair(sdk);
+    await expectTX(
+      await poolWrapper.withSigner(outsider).recoverTokens(stray, mintA),
+      "Recover tokens as outsider"
+    ).to.be.rejected;
+  });
+
+  it("Recover lamports above the rent-exempt minimum", async () => {
+    await expectTX(
+      sdk.newTx([
+        SystemProgram.transfer({
+          fromPubkey: provider.wallet.publicKey,
+          toPubkey: poolWrapper.key,
+          lamports: LAMPORTS_PER_SOL,
+        }),
+      ]),
+      "Send lamports to the pool"
+    ).to.be.fulfilled;
+
+    const outsider = await createFundedKeypair(sdk);
+    await expectTX(
+      await poolWrapper.withSigner(outsider).recoverLamports(),
+      "Recover lamports as outsider"
+    ).to.be.rejected;
+
+    const before = await provider.connection.getBalance(beneficiary.publicKey);
+    await expectTX(await poolWrapper.recoverLamports(), "Recover lamports").to
+      .be.fulfilled;
+    const after = await provider.connection.getBalance(beneficiary.publicKey);
+    expect(after - before).to.equal(LAMPORTS_PER_SOL);
+
+    const poolInfo = await provider.connection.getAccountInfo(poolWrapper.key);
+    expect(poolInfo?.lamports).to.equal(
+      await provider.connection.getMinimumBalanceForRentExemption(
+        poolInfo?.data.length ?? 0
+      )
+    );
+  });
+});
@@ -0,0 +1,115 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { RECOMMENDED_FEES } from "@saberhq/stableswap-sdk";
+import { u64 } from "@saberhq/token-utils";
+import type { Keypair } from "@solana/web3.js";
+import BN from "bn.js";
+import { expect } from "chai";
+
+import { MIN_RAMP_DURATION, ROLES } from "../src/constants";
+import type { PoolWrapper } from "../src/wrappers/pool";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { makePoolManagerSDK } from "./testutils";
+import { createFundedKeypair, setupManagerWithPool } from "./workspace";
+
+describe("Saber Pool Manager roles", () => {
+  const sdk = makePoolManagerSDK();
+
+  let pmWrapper: PoolManagerWrapper;
+  let adminManager: PoolManagerWrapper;
+  let poolWrapper: PoolWrapper;
+  let grantee: Keypair;
+  let granteePool: PoolWrapper;
+
+  beforeEach(async () => {
+    // fees may be set immediately, so only missing roles reject them
+    ({ pmWrapper, adminManager, poolWrapper } = await setupManagerWithPool(
+      sdk,
+      { minFeeNoticePeriod: 0 }
+    ));
+    grantee = await createFundedKeypair(sdk);
+    granteePool = poolWrapper.withSigner(grantee);
+  });
+
+  async function grantRoles(roles: number, validUntil?: BN) {
+    const { roleGrant, tx } = adminManager.createRoleGrant(
+      grantee.publicKey,
+      roles,
+      validUntil
+    );
+    await expectTX(tx, "Create role grant").to.be.fulfilled;
+    return roleGrant;
+  }
+
+  it("A role grant allows exactly its roles", async () => {
+    const roleGrant = await grantRoles(ROLES.PAUSER);
+    const grant = await sdk.programs.Pools.account.roleGrant.fetch(roleGrant);
+    expect(grant.manager).eqAddress(pmWrapper.key);
+    expect(grant.grantee).eqAddress(grantee.publicKey);
+    expect(grant.roles).to.equal(ROLES.PAUSER);
+
+    await expectTX(granteePool.pauseSwap(), "Pause as pauser").to.be
+      .fulfilled;
+    await expectTX(granteePool.unpauseSwap(), "Unpause as pauser").to.be
+      .fulfilled;
+    await expectTX(
+      granteePool.setNewFees(RECOMMENDED_FEES),
+      "Set fees as pauser"
+    ).to.be.rejected;
+    await expectTX(
+      granteePool.rampA(
+        new u64(200),
+        Math.floor(Date.now() / 1_000) + MIN_RAMP_DURATION + 60
+      ),
+      "Ramp as pauser"
+    ).to.be.rejected;
+
+    // replacing the roles replaces the access
+    await expectTX(
+      adminManager.setRoles(grantee.publicKey, ROLES.FEE_SETTER),
+      "Set roles"
+    ).to.be.fulfilled;
+    await expectTX(
+      granteePool.setNewFees(RECOMMENDED_FEES),
+      "Set fees as fee setter"
+    ).to.be.fulfilled;
+    await expectTX(granteePool.pauseSwap(), "Pause as fee setter").to.be
+      .rejected;
+  });
+
+  it("Revoking a role grant removes access", async () => {
+    await grantRoles(ROLES.PAUSER | ROLES.FEE_SETTER);
+    await expectTX(granteePool.pauseSwap(), "Pause as pauser").to.be
+      .fulfilled;
+
+    await expectTX(
+      adminManager.revokeRoleGrant(grantee.publicKey),
+      "Revoke role grant"
+    ).to.be.fulfilled;
+    await expectTX(granteePool.unpauseSwap(), "Unpause after revoking").to.be
+      .rejected;
+    await expectTX(
+      granteePool.setNewFees(RECOMMENDED_FEES),
+      "Set fees after revoking"
+    ).to.be.rejected;
+  });
+
+  it("Expired role grants give no access", async () => {
+    await grantRoles(ROLES.PAUSER, new BN(Math.floor(Date.now() / 1_000) - 1));
+    await expectTX(granteePool.pauseSwap(), "Pause with expired grant").to.be
+      .rejected;
+  });
+
+  it("Only the admin may grant known roles", async () => {
+    // the provider wallet is not the admin
+    await expectTX(
+      pmWrapper.createRoleGrant(grantee.publicKey, ROLES.PAUSER).tx,
+      "Grant roles as outsider"
+    ).to.be.rejected;
+    await expectTX(
+      adminManager.createRoleGrant(grantee.publicKey, ROLES.ALL + 1).tx,
+      "Grant unknown roles"
+    ).to.be.rejected;
+  });
+});
@@ -37,11 +37,16 @@ export const makePoolManagerSDK = (): PoolManagerSDK => {
   return PoolManagerSDK.load({ provider });
 };
 
+/**
+ * Creates a pool of two new mints, or of `existingMintA` minted by `minter`
+ * and a new mint.
+ */
 export const createPool = async (
   provider: Provider,
   minter: Signer,
   poolManagerWrapper: PoolManagerWrapper,
-  initialAmpFactor: u64
+  initialAmpFactor: u64,
+  existingMintA?: PublicKey
 ): Promise<{
   mintA: PublicKey;
   mintB: PublicKey;
@@ -49,11 +54,9 @@ export const createPool = async (
   pool: PublicKey;
   swapAccount: PublicKey;
 }> => {
-  const mintA = await createMint(
-    provider,
-    minter.publicKey,
-    DEFAULT_TOKEN_DECIMALS
-  );
+  const mintA =
+    existingMintA ??
+    (await createMint(provider, minter.publicKey, DEFAULT_TOKEN_DECIMALS));
   const mintB = await createMint(
     provider,
     minter.publicKey,
@@ -121,3 +124,6 @@ export const createPool = async (
 
   return { mintA, mintB, mintLP, pool: poolKey, swapAccount };
 };
+
+export const sleep = (ms: number): Promise<void> =>
+  new Promise((resolve) => setTimeout(resolve, ms));
@@ -0,0 +1,164 @@
+/// <reference types="mocha" />
+
+import { expectTX } from "@saberhq/chai-solana";
+import { StableSwap } from "@saberhq/stableswap-sdk";
+import { u64 } from "@saberhq/token-utils";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { expect } from "chai";
+
+import { MIN_RAMP_DURATION } from "../src/constants";
+import type { PoolManagerWrapper } from "../src/wrappers/poolManager";
+import { makePoolManagerSDK, sleep } from "./testutils";
+import { createFundedKeypair, setupManagerWithPool } from "./workspace";
+
+describe("Saber Pool Manager timelock", () => {
+  const sdk = makePoolManagerSDK();
+  const timelockDelay = 2;
+
+  let admin: Keypair;
+  let pmWrapper: PoolManagerWrapper;
+  let adminManager: PoolManagerWrapper;
+  let pool: PublicKey;
+
+  beforeEach(async () => {
+    ({ admin, pmWrapper, adminManager, pool } = await setupManagerWithPool(
+      sdk
+    ));
+
+    await expectTX(
+      adminManager.setTimelockDelay(timelockDelay),
+      "Enable timelock"
+    ).to.be.fulfilled;
+  });
+
+  async function loadTimelockedAction(key: PublicKey) {
+    return await sdk.programs.Pools.account.timelockedAction.fetch(key);
+  }
+
+  it("Timelock delay may only be changed through the timelock", async () => {
+    const manager = await pmWrapper.reloadData();
+    expect(manager.timelockDelay.toNumber()).to.equal(timelockDelay);
+
+    await expectTX(
+      adminManager.setTimelockDelay(0),
+      "Set timelock delay directly"
+    ).to.be.rejected;
+
+    const { timelockedAction, tx } = await adminManager.queueTimelockedAction({
+      setTimelockDelay: { timelockDelay: new u64(0) },
+    });
+    await expectTX(tx, "Queue timelock delay change").to.be.fulfilled;
+
+    await sleep((timelockDelay + 2) * 1_000);
+    await expectTX(
+      pmWrapper.executeTimelockedManagerAction(timelockedAction),
+      "Execute timelock delay change"
+    ).to.be.fulfilled;
+    const updated = await pmWrapper.reloadData();
+    expect(updated.timelockDelay.toNumber()).to.equal(0);
+  });
+
+  it("Queue and execute a timelocked action", async () => {
+    const newBeneficiary = Keypair.generate();
+    const { timelockedAction, tx } = await adminManager.queueTimelockedAction({
+      setBeneficiary: { beneficiary: newBeneficiary.publicKey },
+    });
+    await expectTX(tx, "Queue beneficiary change").to.be.fulfilled;
+
+    const queued = await loadTimelockedAction(timelockedAction);
+    expect(queued.manager).eqAddress(pmWrapper.key);
+    expect(queued.index.toString()).to.equal("0");
+    expect(queued.proposer).eqAddress(admin.publicKey);
+    const { numTimelockedActions } = await pmWrapper.reloadData();
+    expect(numTimelockedActions.toString()).to.equal("1");
+
+    // the action may not be executed before its eta
+    await expectTX(
+      pmWrapper.executeTimelockedManagerAction(timelockedAction),
+      "Execute early"
+    ).to.be.rejected;
+
+    await sleep((timelockDelay + 2) * 1_000);
+    // swap actions may not execute manager actions
+    await expectTX(
+      await pmWrapper.executeTimelockedSwapAction(timelockedAction, pool),
+      "Execute as swap action"
+    ).to.be.rejected;
+    await expectTX(
+      pmWrapper.executeTimelockedManagerAction(timelockedAction),
+      "Execute beneficiary change"
+    ).to.be.fulfilled;
+
+    const executed = await loadTimelockedAction(timelockedAction);
+    expect(executed.executedAt.toNumber()).to.be.greaterThan(0);
+    // the beneficiary must still accept
+    const manager = await pmWrapper.reloadData();
+    expect(manager.pendingBeneficiary).eqAddress(newBeneficiary.publicKey);
+    expect(manager.beneficiary).eqAddress(admin.publicKey);
+
+    await expectTX(
+      pmWrapper.executeTimelockedManagerAction(timelockedAction),
+      "Execute twice"
+    ).to.be.rejected;
+  });
+
+  it("Cancel a timelocked action", async () => {
+    const { timelockedAction, tx } = await adminManager.queueTimelockedAction({
+      setBeneficiary: { beneficiary: Keypair.generate().publicKey },
+    });
+    await expectTX(tx, "Queue beneficiary change").to.be.fulfilled;
+
+    const outsider = await createFundedKeypair(sdk);
+    await expectTX(
+      pmWrapper.withSigner(outsider).cancelTimelockedAction(timelockedAction),
+      "Cancel as outsider"
+    ).to.be.rejected;
+
+    await expectTX(
+      adminManager.cancelTimelockedAction(timelockedAction),
+      "Cancel as admin"
+    ).to.be.fulfilled;
+    const canceled = await loadTimelockedAction(timelockedAction);
+    expect(canceled.canceledAt.toNumber()).to.be.greaterThan(0);
+
+    await sleep((timelockDelay + 2) * 1_000);
+    await expectTX(
+      pmWrapper.executeTimelockedManagerAction(timelockedAction),
+      "Execute canceled action"
+    ).to.be.rejected;
+    const manager = await pmWrapper.reloadData();
+    expect(manager.pendingBeneficiary).eqAddress(PublicKey.default);
+  });
+
+  it("Ramps may be stopped while timelocked", async () => {
+    // ramp before the timelock is enabled
+    const { adminManager: rampManager, poolWrapper } =
+      await setupManagerWithPool(sdk);
+    const stopRampTs =
+      Math.floor(Date.now() / 1_000) + MIN_RAMP_DURATION + 60;
+    await expectTX(
+      poolWrapper.rampA(new u64(200), stopRampTs),
+      "Ramp amplification coefficient"
+    ).to.be.fulfilled;
+    await expectTX(
+      rampManager.setTimelockDelay(timelockDelay),
+      "Enable timelock"
+    ).to.be.fulfilled;
+
+    // starting a ramp requires the timelock, but stopping one does not
+    await expectTX(
+      poolWrapper.rampA(new u64(150), stopRampTs),
+      "Ramp while timelocked"
+    ).to.be.rejected;
+    await expectTX(poolWrapper.stopRampA(), "Stop ramp while timelocked").to
+      .be.fulfilled;
+
+    const { state } = await StableSwap.load(
+      sdk.provider.connection,
+      poolWrapper.data.swap
+    );
+    expect(state.targetAmpFactor.toString()).to.equal(
+      state.initialAmpFactor.toString()
+    );
+  });
+});
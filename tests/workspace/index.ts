@@ -0,0 +1,204 @@
+import { assertTXSuccess, expectTX } from "@saberhq/chai-solana";
+import type { Provider } from "@saberhq/solana-contrib";
+import type { Fees, StableSwap } from "@saberhq/stableswap-sdk";
+import {
+  DEFAULT_TOKEN_DECIMALS,
+  deployNewSwap,
+  SWAP_PROGRAM_ID,
+} from "@saberhq/stableswap-sdk";
+import {
+  createMint,
+  SPLToken,
+  TOKEN_PROGRAM_ID,
+  u64,
+} from "@saberhq/token-utils";
+import type { PublicKey } from "@solana/web3.js";
+import { Keypair, LAMPORTS_PER_SOL } from "@solana/web3.js";
+
+import type { PoolManagerSDK } from "../../src/poolManagerSdk";
+import type { RampPolicy } from "../../src/types";
+import { comparePubkeys } from "../../src/utils/comparePubkeys";
+import type { PoolWrapper } from "../../src/wrappers/pool";
+import type { PoolManagerWrapper } from "../../src/wrappers/poolManager";
+import { createPool } from "../testutils";
+
+/**
+ * A new pool manager with a single pool.
+ */
+export type ManagerWithPool = {
+  admin: Keypair;
+  minter: Keypair;
+  /**
+   * Manager signed by the provider wallet, which is not the admin.
+   */
+  pmWrapper: PoolManagerWrapper;
+  /**
+   * Manager signed by the admin.
+   */
+  adminManager: PoolManagerWrapper;
+  /**
+   * Pool signed by the admin.
+   */
+  poolWrapper: PoolWrapper;
+  mintA: PublicKey;
+  mintB: PublicKey;
+  mintLP: PublicKey;
+  pool: PublicKey;
+  swapAccount: PublicKey;
+};
+
+/**
+ * Creates a pool manager administered by a new funded keypair,
+ * then creates a pool on it.
+ *
+ * The fee notice period, the swap admin change delay and the ramp policy may
+ * only be changed immediately before the first pool exists, so they are set
+ * before the pool is created.
+ */
+export const setupManagerWithPool = async (
+  sdk: PoolManagerSDK,
+  {
+    initialAmpFactor = new u64(100),
+    minFeeNoticePeriod,
+    swapAdminChangeDelay,
+    rampPolicy,
+  }: {
+    initialAmpFactor?: u64;
+    minFeeNoticePeriod?: number;
+    swapAdminChangeDelay?: number;
+    rampPolicy?: RampPolicy;
+  } = {}
+): Promise<ManagerWithPool> => {
+  const { provider } = sdk;
+  const admin = Keypair.generate();
+  const minter = Keypair.generate();
+
+  const { tx, wrapper } = await sdk.newManager({ admin: admin.publicKey });
+  await assertTXSuccess(tx, "Create new manager");
+
+  await expectTX(
+    provider.withSigner(admin).requestAirdrop(100 * LAMPORTS_PER_SOL)
+  ).to.be.fulfilled;
+  const adminManager = wrapper.withSigner(admin);
+  if (minFeeNoticePeriod !== undefined) {
+    await expectTX(
+      adminManager.setMinFeeNoticePeriod(minFeeNoticePeriod),
+      "Set fee notice period"
+    ).to.be.fulfilled;
+  }
+  if (swapAdminChangeDelay !== undefined) {
+    await expectTX(
+      adminManager.setSwapAdminChangeDelay(swapAdminChangeDelay),
+      "Set swap admin change delay"
+    ).to.be.fulfilled;
+  }
+  if (rampPolicy !== undefined) {
+    await expectTX(
+      adminManager.commitNewRampPolicy(rampPolicy),
+      "Set ramp policy"
+    ).to.be.fulfilled;
+  }
+
+  const { mintA, mintB, mintLP, pool, swapAccount } = await createPool(
+    provider,
+    minter,
+    wrapper,
+    initialAmpFactor
+  );
+  const poolWrapper = await adminManager.loadPoolWrapper(pool);
+
+  return {
+    admin,
+    minter,
+    pmWrapper: wrapper,
+    adminManager,
+    poolWrapper,
+    mintA,
+    mintB,
+    mintLP,
+    pool,
+    swapAccount,
+  };
+};
+
+/**
+ * Funds a new keypair so that it may pay for its own transactions.
+ */
+export const createFundedKeypair = async (
+  sdk: PoolManagerSDK,
+  lamports = LAMPORTS_PER_SOL
+): Promise<Keypair> => {
+  const keypair = Keypair.generate();
+  await expectTX(sdk.provider.withSigner(keypair).requestAirdrop(lamports))
+    .to.be.fulfilled;
+  return keypair;
+};
+
+/**
+ * Deploys a swap outside of any pool manager, administered by `admin`
+ * and seeded with tokens minted by `minter`.
+ * With `unsortedMints`, token A of the swap has the greater mint.
+ */
+export const deploySwap = async (
+  provider: Provider,
+  minter: Keypair,
+  {
+    admin = minter.publicKey,
+    ampFactor,
+    fees,
+    unsortedMints = false,
+  }: {
+    admin?: PublicKey;
+    ampFactor: u64;
+    fees?: Fees;
+    unsortedMints?: boolean;
+  }
+): Promise<{ swap: StableSwap; mintA: PublicKey; mintB: PublicKey }> => {
+  let mintA = await createMint(
+    provider,
+    minter.publicKey,
+    DEFAULT_TOKEN_DECIMALS
+  );
+  let mintB = await createMint(
+    provider,
+    minter.publicKey,
+    DEFAULT_TOKEN_DECIMALS
+  );
+  if (unsortedMints && comparePubkeys(mintA, mintB) === -1) {
+    [mintA, mintB] = [mintB, mintA];
+  }
+
+  const { swap } = await deployNewSwap({
+    provider,
+    swapProgramID: SWAP_PROGRAM_ID,
+
+    tokenAMint: mintA,
+    tokenBMint: mintB,
+    adminAccount: admin,
+    ampFactor,
+    fees,
+
+    seedPoolAccounts: ({ tokenAAccount, tokenBAccount }) => ({
+      instructions: [
+        SPLToken.createMintToInstruction(
+          TOKEN_PROGRAM_ID,
+          mintA,
+          tokenAAccount,
+          minter.publicKey,
+          [],
+          1_000_000
+        ),
+        SPLToken.createMintToInstruction(
+          TOKEN_PROGRAM_ID,
+          mintB,
+          tokenBAccount,
+          minter.publicKey,
+          [],
+          1_000_000
+        ),
+      ],
+      signers: [minter],
+    }),
+  });
+  return { swap, mintA, mintB };
+};